@@ -47,6 +47,18 @@ macro_rules! compose_struct_interface { ()=>{
 		//! }
 		//! ```
 		//!
+		//! * アトリビュートは書かれた順序を保って出力されます。複数の `derive` や `allow` は最初に現れた位置で1つにまとめられ、親から継承されるアトリビュートは子に書かれたアトリビュートよりも前に付加されます
+		//!
+		//! * `repr` は子の構造体/列挙体にとって正しいとは限らないので既定では継承されませんが、 `#[repr_inherit]` を併せて指定すると継承されます
+		//!
+		//! ```rust
+		//! #[repr(C)]
+		//! #[repr_inherit]
+		//! struct Header {
+		//! 	kind = enum HeaderKind { A, B } // #[repr(C)] が継承される
+		//! }
+		//! ```
+		//!
 		//! #### デフォルト値を指定できる
 		//! * 別途 `impl Default` を用意しなくても対にして定義できます
 		//!
@@ -80,6 +92,12 @@ macro_rules! compose_struct_interface { ()=>{
 		//! }
 		//! ```
 		//!
+		//! * タプル構造体でも同様に、各フィールドの型の後にデフォルト値を指定できます
+		//!
+		//! ```rust
+		//! struct Pair(i32 = 3, #[default] String); // Pair(3,String::new()) がデフォルト値
+		//! ```
+		//!
 		//! #### 型やトレイトのエイリアスを指定可能
 		//! * 通常通り型の定義ができるのはもちろんのこと、 stable でないトレイトのエイリアスも用意できます。
 		//!
@@ -96,6 +114,14 @@ macro_rules! compose_struct_interface { ()=>{
 		//! }
 		//! ```
 		//!
+		//! * 型エイリアスにも `where` 節を付けられます。 stable の Rust では型エイリアスの境界は強制されない (ドキュメントとしての意味しか持たない) ため、 `type_alias_bounds` の警告を抑制した上で `type Foo<T> where T: Clone = Vec<T>;` の形で出力されます。境界を強制するには nightly の `lazy_type_alias` が必要です。
+		//!
+		//! ```rust
+		//! compose_struct! {
+		//! 	type CloneVec<T> = Vec<T> where T: Clone;
+		//! }
+		//! ```
+		//!
 		//! * 構造体や列挙体の内部で型エイリアスを定義することもできます。フィールドの近くに配置できるので関係性が視覚的にわかりやすくなります。
 		//!
 		//! ```rust
@@ -112,6 +138,34 @@ macro_rules! compose_struct_interface { ()=>{
 		//! }
 		//! ```
 		//!
+		//! #### フィールドのない列挙体に文字列変換と全バリアントの列挙を付加できる
+		//! * 全てのバリアントがフィールドを持たない列挙体に `#[enum_str]` アトリビュートを付すと、以下が生成されます
+		//! 	* バリアント名を返す `fn as_str(&self) -> &'static str`
+		//! 	* 全てのバリアントを定義順に並べた `const ALL: &'static [Self]`
+		//! 	* 全てのバリアントを定義順に返すイテレータ `fn variants() -> impl Iterator<Item=Self>`
+		//! * フィールドを持つバリアントが含まれる場合や、構造体に付した場合はエラーになります
+		//!
+		//! ```rust
+		//! #[enum_str]
+		//! enum Direction { North, East, South, West }
+		//! // Direction::South.as_str() == "South"
+		//! ```
+		//!
+		//! #### 条件を満たす値のみを保持するラッパー型を生成できる
+		//! * フィールドを1つだけ持つタプル構造体に `#[validated(条件式)]` アトリビュートを付すと、以下が生成されます
+		//! 	* 条件式を満たす場合のみ値を生成する `fn new(value:T) -> Result<Self,ValidationError>`
+		//! 	* 内部の値を取り出す `fn into_inner(self) -> T`
+		//! 	* 内部の型への `Deref`
+		//! * 条件式の中では、検証する値を `value` (`&T` 型) で参照します
+		//! * `new` を経由せずに値を作れないよう、フィールドは非公開になります
+		//! * 生成されるコードは `ValidationError` を参照するので、 `utils::ValidationError` をスコープに入れておく必要があります
+		//!
+		//! ```rust
+		//! #[validated(*value>0.0)]
+		//! struct Positive(f64);
+		//! // Positive::new(-1.0) は Err(..) になる
+		//! ```
+		//!
 		//! ### 例
 		//! ```rust
 		//! compose_struct! {
@@ -306,6 +360,8 @@ mod typedef {
 		pub attributes: Vec<Attr>,
 		/// `pub` などの可視性 (エイリアスにアクセス可能な範囲) の情報
 		pub visibility: TS,
+		/// `where` によるジェネリクスの拘束条件
+		pub where_condition: TS,
 		/// 元のソースコード
 		pub src: String
 	}
@@ -343,6 +399,14 @@ mod typedef {
 		Default,
 		/// `#[pub_all]` アトリビュート
 		PubAll,
+		/// `#[enum_str]` アトリビュート
+		EnumStr,
+		/// `#[repr(..)]` アトリビュート
+		Repr(TS),
+		/// `#[repr_inherit]` アトリビュート (`repr` を子の構造体/列挙体に継承させる)
+		ReprInherit,
+		/// `#[validated(..)]` アトリビュート (値を検証する `new` を生成する)
+		Validated(TS),
 		/// その他の全てのアトリビュート
 		Other(TS)
 	}
@@ -757,7 +821,7 @@ mod parser {
 							wh = quote!( #wh #t );
 						}
 					},
-					(PP::GotArtifact,"where",_,K::TraitAlias) => {
+					(PP::GotArtifact,"where",_,K::TypeAlias|K::TraitAlias) => {
 						phase = PP::GotWhere;
 					},
 					(PP::GotEqual|PP::GotArtifact,_,t,K::TypeAlias|K::TraitAlias) => {
@@ -1502,7 +1566,7 @@ mod parser {
 	impl ParseFrom<ParsingResult,Self> for TypeAlias {
 		fn parse_from(pr:ParsingResult) -> Self {
 			let ParsingResult {
-				name, mut generics, body, attr, vis, src, ..
+				name, mut generics, body, attr, vis, wh, src, ..
 			} = pr;
 			if !generics.is_empty() {
 				generics = quote!( <#generics> );
@@ -1513,6 +1577,7 @@ mod parser {
 				artifact: body,
 				attributes: attr,
 				visibility: vis,
+				where_condition: wh,
 				src
 			}
 		}
@@ -1555,6 +1620,8 @@ mod parser {
 			let mut a = match &kind[..] {
 				"default" => Self::Default,
 				"pub_all" => Self::PubAll,
+				"enum_str" => Self::EnumStr,
+				"repr_inherit" => Self::ReprInherit,
 				_ => Self::Other(ts.clone())
 			};
 
@@ -1586,6 +1653,14 @@ mod parser {
 						a = Self::Cfg(g.stream());
 						phase = PP::GotGroup;
 					},
+					(PP::Beginning,"repr",Self::Other(_),_,TT::Group(g)) => {
+						a = Self::Repr(g.stream());
+						phase = PP::GotGroup;
+					},
+					(PP::Beginning,"validated",Self::Other(_),_,TT::Group(g)) => {
+						a = Self::Validated(g.stream());
+						phase = PP::GotGroup;
+					},
 					(PP::Beginning,"doc",Self::Other(_),"=",_) => {
 						phase = PP::GotEqual;
 					},
@@ -1669,6 +1744,9 @@ mod modification {
 			self.check_pub_all();
 			self.check_default();
 
+			// 複数に分かれた derive や allow は、子の構造体/列挙体にコピーする前に1つにまとめておく
+			remove_duplicate(&mut self.attributes);
+
 			// フィールドの種類によらない抽象化
 			impl Struct {
 				fn exchange_with_fields<F: GetSubtype>(&mut self,fields:&mut CapsuledFields<F>) {
@@ -1699,8 +1777,6 @@ mod modification {
 				F::Named(f) => st.exchange_with_fields(f)
 			}
 
-			remove_duplicate(&mut self.attributes);
-
 			fields.modify();
 		}
 	}
@@ -1718,13 +1794,14 @@ mod modification {
 				..
 			} = self;
 
+			// 複数に分かれた derive や allow は、子の構造体/列挙体にコピーする前に1つにまとめておく
+			remove_duplicate(attributes);
+
 			let mut st = variants.collect_subtype();
 			st.extend(enclosed.iter_mut());
 
 			copy_attr_to_subtype(&*attributes,&mut st);
 
-			remove_duplicate(attributes);
-
 			st.iter_mut()
 			.for_each(|d| {
 				inherit_visibility(visibility,*d);
@@ -2163,8 +2240,14 @@ mod modification {
 		let mut copied_derive:Vec<Ident> = vec![];
 		let mut copied_allow:Vec<Ident> = vec![];
 		let mut copied_cfg:Vec<TS> = vec![];
+		// `repr` は子にとって正しいとは限らないので、 `#[repr_inherit]` が指定された場合のみ継承する
+		let inherit_repr = pal.iter().any(|a| matches!(a,Attr::ReprInherit) );
+		let mut copied_repr:Vec<TS> = vec![];
 		for a in pal.iter() {
 			match a {
+				Attr::Repr(r) if inherit_repr => {
+					copied_repr.push(r.clone());
+				},
 				Attr::Allow(v) => {
 					copied_allow.extend(v.clone());
 				},
@@ -2196,8 +2279,9 @@ mod modification {
 				Data::Debug => { unreachable!(); }
 			};
 
-			// derive, allow, cfg は他のアトリビュートよりも影響が大きいことが多いので、他のアトリビュートよりも前に追加する
-			// そのために、一時的的なリストに順に追加してから、置き換える
+			// 継承されるアトリビュートは子に書かれたアトリビュートよりも前に追加する
+			// derive を前に置かないと、子に書かれた derive ヘルパーアトリビュート (serde など) が derive よりも前に来てしまう
+			// 子に書かれたアトリビュート同士の順序 (アトリビュートマクロの適用順など) は変えない
 			let mut ca_tmp:Vec<Attr> = vec![];
 
 			for c in copied_cfg.iter() {
@@ -2215,8 +2299,17 @@ mod modification {
 					Attr::Derive(copied_derive.clone())
 				);
 			}
+			if will_copy_derive && inherit_repr {
+				for r in copied_repr.iter() {
+					ca_tmp.push(
+						Attr::Repr(r.clone())
+					);
+				}
+				ca_tmp.push(Attr::ReprInherit);
+			}
 
 			ca_tmp.append(ca);
+
 			swap(ca,&mut ca_tmp);
 		}
 	}
@@ -2255,10 +2348,12 @@ mod modification {
 		to_attr:impl Fn(Vec<Ident>) -> Attr
 	) {
 		let num = attr.iter()
-		.filter(is_matched)
+		.filter(&is_matched)
 		.count();
 		if num<2 { return; }
 
+		// まとめたアトリビュートは、最初に現れた位置に置く (他のアトリビュートとの相対的な順序を保つ)
+		let first = attr.iter().position(|a| is_matched(&a) ).unwrap();
 		let mut items:Vec<Ident> = vec![];
 
 		let mut attr_tmp:Vec<Attr> = vec![];
@@ -2281,13 +2376,7 @@ mod modification {
 		items.dedup_by(|i1,i2| i1.to_string()==i2.to_string() );
 
 		if items.len()>0 {
-			let a = to_attr(items);
-			if attr.is_empty() { attr.push(a); }
-			else {
-				let mut attr_tmp = vec![a];
-				attr_tmp.append(attr);
-				swap(attr,&mut attr_tmp);
-			}
+			attr.insert(first,to_attr(items));
 		}
 	}
 
@@ -2298,6 +2387,121 @@ mod modification {
 		.map(|i| attr.remove(i) )
 	}
 
+	#[cfg(test)]
+	#[test]
+	fn test_merged_derive() {
+		let src = quote! {
+			#[derive(Clone)]
+			#[derive(Debug,Hash,Clone)]
+			enum Parent {
+				Child( struct Child { x:i32 } )
+			}
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		// 親は1つの derive にまとめられ、子には伝播可能なもの (Clone, Debug) のみが重複なく付く
+		assert!( generated.contains("# [derive (Clone , Debug , Hash)] enum Parent") );
+		assert!( generated.contains("# [derive (Clone , Debug)] struct Child") );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_attr_order() {
+		let src = quote! {
+			#[serde(rename_all="camelCase")]
+			#[derive(Serialize)]
+			#[must_use]
+			#[derive(Clone)]
+			#[cfg(feature="serde")]
+			struct Parent {
+				child = struct Child { x:i32 }
+			}
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		// まとめた derive は最初に現れた位置に置かれ、その他のアトリビュートの順序は保たれる
+		assert!( generated.contains(&quote!(
+			#[serde(rename_all="camelCase")]
+			#[derive(Clone,Serialize)]
+			#[must_use]
+			#[cfg(feature="serde")]
+			struct Parent
+		).to_string()) );
+		// 子では継承された cfg, derive が前に置かれる
+		assert!( generated.contains(&quote!(
+			#[cfg(feature="serde")]
+			#[derive(Clone,Serialize)]
+			struct Child
+		).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_inherited_derive_before_helper() {
+		let src = quote! {
+			#[derive(Serialize)]
+			struct Parent {
+				child = struct Child {
+					#![serde(rename_all="camelCase")]
+					#![must_use]
+					field_x:i32
+				}
+			}
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		// derive ヘルパーアトリビュートが継承された derive よりも前に来てはならない
+		assert!( generated.contains(&quote!(
+			#[derive(Serialize)]
+			#[serde(rename_all="camelCase")]
+			#[must_use]
+			struct Child
+		).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_repr_inheritance() {
+		let src = quote! {
+			#[repr(C, align(8))]
+			struct Parent {
+				child = enum Child { A, B }
+			}
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		// 既定では継承しない
+		assert!( generated.contains(&quote!( #[repr(C, align(8))] struct Parent ).to_string()) );
+		assert!( generated.contains(&quote!( enum Child { A, B, } ).to_string()) );
+		assert_eq!( generated.matches("repr").count(), 1 );
+
+		let src = quote! {
+			#[repr(C)]
+			#[repr_inherit]
+			struct Parent {
+				child = struct Child {
+					grandchild = enum GrandChild { A, B }
+				}
+			}
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		assert!( generated.contains(&quote!( #[repr(C)] struct Parent ).to_string()) );
+		assert!( generated.contains(&quote!( #[repr(C)] struct Child ).to_string()) );
+		assert!( generated.contains(&quote!( #[repr(C)] enum GrandChild ).to_string()) );
+		assert!( !generated.contains("repr_inherit") );
+	}
+
 }
 use modification::*;
 
@@ -2369,13 +2573,28 @@ mod compose {
 				}
 			};
 
+			if self.attributes.iter().any(|a| matches!(a,Attr::EnumStr) ) {
+				error("#[enum_str] は列挙体にのみ指定できます",Some(&self.src));
+			}
+			let validated = self.attributes.iter().find_map(|a| match a {
+				Attr::Validated(p) => Some(p),
+				_ => None
+			});
+
 			{
 				let a = self.attributes.compose(global);
 				let v = &self.visibility;
 				let w = add_where(&self.where_condition.clone());
 				let mut this = quote!( #a #v struct #n #g #w );
 
-				let f = self.fields.compose(global);
+				let f = match validated {
+					Some(p) => {
+						let (f,validation) = self.compose_validated(p,&g,global);
+						*global = quote!( #global #validation );
+						f
+					},
+					None => self.fields.compose(global)
+				};
 				this = quote!( #this #f );
 
 				type F = Fields;
@@ -2425,6 +2644,10 @@ mod compose {
 				}
 			};
 
+			if self.attributes.iter().any(|a| matches!(a,Attr::Validated(_)) ) {
+				error("#[validated(..)] はタプル構造体にのみ指定できます",Some(&self.src));
+			}
+
 			{
 				let a = self.attributes.compose(global);
 				let v = &self.visibility;
@@ -2462,6 +2685,11 @@ mod compose {
 				*global = quote!( #global #this );
 			}
 
+			if self.attributes.iter().any(|a| matches!(a,Attr::EnumStr) ) {
+				let this = self.compose_enum_str(&g);
+				*global = quote!( #global #this );
+			}
+
 			for d in self.enclosed.iter() {
 				d.compose(global);
 			}
@@ -2473,6 +2701,154 @@ mod compose {
 		}
 	}
 
+	impl Struct {
+		/// `#[validated(..)]` アトリビュートにより、非公開にしたフィールドと、付加する `new`, `into_inner`, `Deref` を生成
+		fn compose_validated(&self,predicate:&TS,g:&TS,global:&mut TS) -> (TS,TS) {
+			let field = match &self.fields {
+				Fields::Unnamed(f) if f.fields.len()==1 => &f.fields[0],
+				_ => error("#[validated(..)] はフィールドを1つだけ持つタプル構造体にのみ指定できます",Some(&self.src))
+			};
+			for d in self.fields_enclosed() {
+				d.compose(global);
+			}
+
+			let n = &self.name;
+			let fa = field.attributes.compose(global);
+			let t = field.value.compose(global);
+			let a = self.attributes.compose_default(global);
+			let w = add_where(&self.where_condition);
+			let type_name = n.to_string();
+			let predicate_str = predicate.to_string();
+			let validation = quote!(
+				#a impl #g #n #g #w {
+					/// 値が条件を満たす場合のみ生成します
+					pub fn new(value: #t) -> Result<Self,ValidationError> {
+						let valid = {
+							let value = &value;
+							#predicate
+						};
+						match valid {
+							true => Ok(Self(value)),
+							false => Err(ValidationError { type_name: #type_name, predicate: #predicate_str })
+						}
+					}
+					/// 内部の値を取り出します
+					pub fn into_inner(self) -> #t { self.0 }
+				}
+				#a impl #g std::ops::Deref for #n #g #w {
+					type Target = #t;
+					fn deref(&self) -> &Self::Target { &self.0 }
+				}
+			);
+			( quote!( ( #fa #t, ) ), validation )
+		}
+
+		/// フィールドの中で定義された別のデータ型のリスト
+		fn fields_enclosed(&self) -> &[Data] {
+			match &self.fields {
+				Fields::Unit => &[],
+				Fields::Unnamed(f) => &f.enclosed,
+				Fields::Named(f) => &f.enclosed
+			}
+		}
+	}
+
+	impl Enum {
+		/// `#[enum_str]` アトリビュートにより付加する `as_str`, `ALL`, `variants` を生成
+		fn compose_enum_str(&self,g:&TS) -> TS {
+			if let Some(v) = self.variants.iter().find(|v| !matches!(v.fields,Fields::Unit) ) {
+				error(
+					format!("#[enum_str] を指定した列挙体のバリアント {} にフィールドが含まれています",v.name),
+					Some(&v.src)
+				);
+			}
+
+			let n = &self.name;
+			let a = self.attributes.compose_default(&mut TS::new());
+			let w = add_where(&self.where_condition);
+			let names = self.variants.iter().map(|v| &v.name ).collect::<Vec<_>>();
+			let strs = names.iter().map(|n| n.to_string() );
+			let indices = 0..names.len();
+			let count = names.len();
+			quote!(
+				#a impl #g #n #g #w {
+					/// 全てのバリアントを定義順に並べたもの
+					pub const ALL: &'static [Self] = &[ #( Self::#names ),* ];
+					/// バリアント名を返します
+					pub fn as_str(&self) -> &'static str {
+						match self { #( Self::#names => #strs ),* }
+					}
+					/// 全てのバリアントを定義順に返すイテレータを生成します
+					pub fn variants() -> impl Iterator<Item=Self> {
+						(0..#count).map(|i| match i {
+							#( #indices => Self::#names, )*
+							_ => unreachable!()
+						})
+					}
+				}
+			)
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_enum_str() {
+		let src = quote! {
+			#[enum_str]
+			#[derive(Debug)]
+			pub enum Direction { North, East = default, South }
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		assert!( generated.contains("# [derive (Debug)] pub enum Direction") );
+		assert!( !generated.contains("enum_str") );
+		assert!( generated.contains(&quote!( pub const ALL: &'static [Self] = &[ Self::North, Self::East, Self::South ]; ).to_string()) );
+		assert!( generated.contains(&quote!( match self { Self::North => "North", Self::East => "East", Self::South => "South" } ).to_string()) );
+		assert!( generated.contains(&quote!( 2usize => Self::South, ).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_validated() {
+		let src = quote! {
+			#[validated(*value>0)]
+			#[derive(Debug)]
+			pub struct Positive(pub i32);
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		let generated = compose(root).to_string();
+
+		assert!( generated.contains(&quote!( #[derive(Debug)] pub struct Positive(i32,); ).to_string()) );
+		assert!( !generated.contains("validated") );
+		assert!( generated.contains(&quote!( pub fn new(value: i32) -> Result<Self,ValidationError> ).to_string()) );
+		assert!( generated.contains(&quote!( let value = &value; *value>0 ).to_string()) );
+		assert!( generated.contains(&quote!( impl std::ops::Deref for Positive ).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_type_alias_where() {
+		let src = quote! {
+			pub type Foo<T> = Vec<T> where T: Clone;
+			type Bar = Option<f64>;
+		};
+		let root = parse(src);
+		match &root.datum[0] {
+			Data::Type(t) => {
+				assert_eq!( t.artifact.to_string(), quote!( Vec<T> ).to_string() );
+				assert_eq!( t.where_condition.to_string(), quote!( T: Clone ).to_string() );
+			},
+			_ => panic!("型エイリアスとしてパースされませんでした")
+		}
+		let generated = compose(root).to_string();
+
+		assert!( generated.contains(&quote!( #[allow(type_alias_bounds)] pub type Foo<T> where T: Clone = Vec<T>; ).to_string()) );
+		assert!( generated.contains(&quote!( type Bar = Option<f64>; ).to_string()) );
+	}
+
 	impl Compose for EnumVariant {
 		fn compose(&self,global:&mut TS) -> TS {
 			let a = self.attributes.compose(global);
@@ -2611,10 +2987,18 @@ mod compose {
 				ref artifact,
 				ref attributes,
 				ref visibility,
+				ref where_condition,
 				..
 			} = self;
 			let attr = attributes.compose(global);
-			let this = quote!( #attr #visibility type #name = #artifact; );
+			// stable では `where` 節を型の後に置けず、前に置いた場合も境界は強制されない旨の警告が出る
+			let this = match where_condition.is_empty() {
+				true => quote!( #attr #visibility type #name = #artifact; ),
+				false => quote!(
+					#attr #[allow(type_alias_bounds)]
+					#visibility type #name where #where_condition = #artifact;
+				)
+			};
 			*global = quote!( #global #this );
 			TS::new()
 		}
@@ -2695,10 +3079,13 @@ mod compose {
 				Self::Cfg(ts) => {
 					quote!( #[cfg(#ts)] )
 				},
+				Self::Repr(ts) => {
+					quote!( #[repr(#ts)] )
+				},
 				Self::Doc(doc) => {
 					quote!( #[doc=#doc] )
 				},
-				Self::Default|Self::PubAll => TS::new(),
+				Self::Default|Self::PubAll|Self::EnumStr|Self::ReprInherit|Self::Validated(_) => TS::new(),
 				Self::Other(ts) => {
 					quote!( #[#ts] )
 				}
@@ -2864,5 +3251,25 @@ mod has_default {
 		}
 	}
 
+	#[cfg(test)]
+	#[test]
+	fn test_tuple_struct_default() {
+		let src = quote! {
+			struct Foo(i32 = 3, String = String::new());
+			struct Bar(i32 = 3, #[default] f64, u8 = 1);
+		};
+		let mut root = parse(src);
+		modify(&mut root);
+		for d in root.datum.iter() {
+			let Data::Struct(s) = d else { unreachable!(); };
+			assert!( matches!(s.has_default(),B::TrueRequired) );
+		}
+		let generated = compose(root).to_string();
+
+		// デフォルト値は位置の順に並べられる
+		assert!( generated.contains(&quote!( Self(3,String::new(),) ).to_string()) );
+		assert!( generated.contains(&quote!( Self(3,std::default::Default::default(),1,) ).to_string()) );
+	}
+
 }
 use has_default::*;
@@ -4,11 +4,12 @@ use proc_macro2::{
 	TokenStream as TS,
 	TokenTree as TT,
 	Delimiter as TD,
+	Spacing,
 };
 use syn::{
 	parse2,
 	Expr,ExprRange,ExprTuple,ExprPath,
-	Ident,Meta,Type
+	Ident,Meta,Type,Path
 };
 use quote::{quote,ToTokens};
 
@@ -91,6 +92,63 @@ macro_rules! for_each_interface {
 			//! * `cell` は `&mut T` 型です。
 			//! * 3行目の表式ではループ内の要素アクセスも、ループ外の配列へのアクセスも変数 `a` を使用します
 			//!
+			//! #### `cell_and_index(a)`
+			//! ```rust
+			//! (cell,(i,j)) = cell_and_index(a)
+			//! (cell,index) = cell_and_index(a)
+			//! ```
+			//! * `ndarray` のN次元配列 `a` の各要素と、そのインデクスを同時にイテレートします。
+			//! * `cell` は `&T` 型で、インデクスは1行目の表式では次元の数だけ並べた `usize` 型の変数として、2行目の表式では `(usize,usize,...)` 型のタプルとして受け取ります。
+			//! * 要素のイテレータと `ndarray::indices` を突き合わせて実装されているため、 `ndarray` をクレートの依存関係に含める必要があります。
+			//! * 並列実行への対応状況は `each_nd` と同じで、 `par_for_each` では配列のメモリ配置が標準的 (`a.as_slice()` が `Some(..)` を返す) である必要があります。
+			//! ```rust
+			//! let a = ndarray::array![[1,2],[3,4]];
+			//! let mut found = vec![];
+			//! for_each! {
+			//! 	(x,(i,j)) = cell_and_index(a)
+			//! 	{ found.push((*x,i,j)); }
+			//! }
+			//! assert_eq!(found,vec![(1,0,0),(2,0,1),(3,1,0),(4,1,1)]);
+			//! ```
+			//!
+			//! #### `entries(map)`
+			//! ```rust
+			//! (k,v) = entries(map)
+			//! (k,v) = entries(mut map)
+			//! kv = entries(map)
+			//! ```
+			//! * `HashMap` や `BTreeMap` などのマップの各エントリに対してイテレートします。
+			//! * `k` は `&K` 型、 `v` は `&V` 型 (2行目の表式では `&mut V` 型) です。3行目の表式ではタプル `(&K,&V)` として受け取ります。
+			//! * 直列では `.iter()` / `.iter_mut()` を、並列では `rayon` の `.par_iter()` / `.par_iter_mut()` を使用します。並列で使用するには、マップが `rayon` の `IntoParallelRefIterator` / `IntoParallelRefMutIterator` に対応している必要があります (`HashMap` や `BTreeMap` は `rayon` が対応しています) 。
+			//! * マップの並列イテレータはインデクス付きでないため、 `par_for_each` では他のイテレーション対象と併用できません。
+			//! ```rust
+			//! let map = std::collections::HashMap::from([("a",1),("b",2)]);
+			//! let mut sum = 0;
+			//! for_each! {
+			//! 	(_k,v) = entries(map)
+			//! 	{ sum += v; }
+			//! }
+			//! assert_eq!(sum,3);
+			//! ```
+			//!
+			//! #### `each_window(a,n)`
+			//! ```rust
+			//! w = each_window(a,3)
+			//! ```
+			//! * スライスとして扱える配列 `a` の長さ `n` の重なり合う区間 (`a.windows(n)`) に対してイテレートします。ステンシル計算などに便利です。
+			//! * `w` は `&[T]` 型です。
+			//! * 安全な `windows_mut` が存在しないため、ミュータブルなイテレートには対応していません。
+			//! * `par_for_each` や `par_bridge_for_each` には対応していません。
+			//! ```rust
+			//! let a = [1,2,3,4,5];
+			//! let mut sums = vec![];
+			//! for_each! {
+			//! 	w = each_window(a,3)
+			//! 	{ sums.push(w.iter().sum::<i32>()); }
+			//! }
+			//! assert_eq!(sums,vec![6,9,12]);
+			//! ```
+			//!
 			//! #### `index(n,m,...)`
 			//! ```rust
 			//! n = index(10)
@@ -169,6 +227,20 @@ macro_rules! for_each_interface {
 			//! | `\|\|`, `or` | ブールOR | `false` |
 			//!
 			//! * 単位元の型が判定できないためにコンパイルエラーを発することがあり、その場合は `fold(+:var)` の代わりに `fold(+(f64):var)` などと記載して型を明示することができます。
+			//! * 演算子の代わりに `combine = path` と記載すると、 `Default` を実装した任意の型に対して、2つの値を結合する関数 `path` を用いてリダクションを行います。単位元には `Default::default()` が用いられます。
+			//! ```rust
+			//! #[derive(Default)]
+			//! struct Stats { count: usize, sum: f64 }
+			//! fn merge(a:Stats,b:Stats) -> Stats {
+			//! 	Stats { count: a.count+b.count, sum: a.sum+b.sum }
+			//! }
+			//! let mut stats = Stats::default();
+			//! par_for_each! {
+			//! 	x = each(array)
+			//! 	fold(combine = merge : stats)
+			//! 	{ stats.count += 1; stats.sum += *x; }
+			//! }
+			//! ```
 			//! * OpenMP の挙動に準拠するために、例えば加算であれば外の変数の元々の値にループでの値を足し合わせていきますが、ループの値を足し合わせた結果を外の変数に代入するのであれば `fold` の代わりに `fold_assign` を使用します。
 			//! 	* この場合には、外の変数は `let mut sum:u8;` のように初期化していない状態で定義しておくことも可能です。
 			//!
@@ -202,6 +274,55 @@ macro_rules! for_each_interface {
 			//! * 通常は無条件に並列実行しますが、このオプションが付加されている場合は `condition` を満たす場合 (`#[cfg(condition)]` アトリビュードで無視されない場合) のみ並列に実行されます。
 			//! * 複数個の `par_cond_cfg` オプションが指定された場合は、それら全てを満たす場合にのみ並列に実行されます。
 			//!
+			//! ### `inner_serial()`
+			//! * `par_for_each` や `par_bridge_for_each` であっても、並列化せずに直列のコードを生成します。 `for_each` で指定しても変化はありません。
+			//! * 既に並列に実行されている外側のループの内側でマクロを使用する場合に指定し、並列処理が入れ子になってスレッドが過剰に使われるのを防ぎます。
+			//! ```rust
+			//! par_for_each! {
+			//! 	i = index(n)
+			//! 	{
+			//! 		// 外側のみ並列に実行される
+			//! 		par_for_each! {
+			//! 			j = index(m)
+			//! 			inner_serial()
+			//! 			{ /* 処理内容 */ }
+			//! 		}
+			//! 	}
+			//! }
+			//! ```
+			//!
+			//! ### `rev()`
+			//! * `for_each` の場合に、突き合わせたイテレータを逆順にしてから実行します。配列を末尾から処理する必要のあるインプレースのアルゴリズムなどで使用します。
+			//! * 全てのイテレーション対象が `DoubleEndedIterator` である必要があります。複数の対象を突き合わせる場合は `ExactSizeIterator` でもある必要があります。
+			//! * 並列実行では実行順序が保証されないため、 `par_for_each` や `par_bridge_for_each` で指定するとコンパイルエラーになります。 `inner_serial()` と併用した場合は直列として扱われます。
+			//! ```rust
+			//! for_each! {
+			//! 	i = index(n)
+			//! 	rev()
+			//! 	{ /* i = n-1, n-2, ..., 0 の順に処理される */ }
+			//! }
+			//! ```
+			//!
+			//! ### `with_min_len(n)`, `with_max_len(n)`, `pool(p)`
+			//! ```rust
+			//! with_min_len(n)
+			//! with_max_len(n)
+			//! pool(p)
+			//! ```
+			//! * 並列実行の粒度や実行するスレッドプールを指定します。
+			//! * `with_min_len(n)`, `with_max_len(n)` は、突き合わせた並列イテレータに対して `rayon` の `.with_min_len(n)`, `.with_max_len(n)` を呼び出し、1つのスレッドで直列に処理する要素数の下限と上限を指定します。インデクス付きの並列イテレータである必要があるため、 `par_for_each` でのみ有効で、 `par_bridge_for_each` では無視されます。
+			//! * `pool(p)` は、 `rayon::ThreadPool` 型の値 `p` を与えると、並列処理の全体を `p.install(|| ..)` の中で実行し、グローバルのスレッドプールの代わりに `p` のスレッドを使用します。
+			//! * いずれも直列で実行する場合 (`for_each` や `inner_serial()` を指定した場合、 `par_cond_*` で直列となった場合) は無視されます。
+			//! ```rust
+			//! let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+			//! par_for_each! {
+			//! 	i = index(n)
+			//! 	with_min_len(1024)
+			//! 	pool(pool)
+			//! 	{ /* 処理内容 */ }
+			//! }
+			//! ```
+			//!
 			//! ### `debug()`
 			//! ビルド時にマクロ展開した結果を出力します。コンパイルエラーが発生する場合に原因を探すのに役立ちます。
 			//!
@@ -234,6 +355,14 @@ mod typedef {
 		pub src: String,
 		/// `debug()` がオプションに入っていて、デバッグ出力するかどうか
 		pub debug: bool,
+		/// `rev()` がオプションに入っていて、逆順にイテレートするかどうか
+		pub rev: bool,
+		/// `with_min_len(n)` で指定された、並列実行で直列に処理する要素数の下限
+		pub min_len: Option<Expr>,
+		/// `with_max_len(n)` で指定された、並列実行で直列に処理する要素数の上限
+		pub max_len: Option<Expr>,
+		/// `pool(p)` で指定された、並列実行に使用するスレッドプール
+		pub pool: Option<Expr>,
 		/// 引数のリスト
 		pub args: Vec<Arg>,
 		/// リダクションのモード
@@ -281,6 +410,33 @@ mod typedef {
 			/// イテレートする対象の NDArray
 			array: Expr
 		},
+		/// NDArray の各要素とそのインデクスを同時にイテレートします
+		CellAndIndex {
+			/// ループ内で取り出す要素の変数名
+			var: Ident,
+			/// ループ内で取り出すインデクスのパターン
+			index: TS,
+			/// イテレートする対象の NDArray
+			array: Expr
+		},
+		/// マップのエントリをイテレートします
+		Entries {
+			/// ミュータブルなイテレートか
+			mutable: bool,
+			/// ループ内で取り出す変数のパターン
+			pattern: TS,
+			/// イテレートする対象のマップ
+			map: Expr
+		},
+		/// スライスの重なり合う区間をイテレートします
+		EachWindow {
+			/// ループ内で取り出す変数名
+			var: Ident,
+			/// イテレートする対象の配列
+			array: Expr,
+			/// 区間の長さ
+			size: Expr
+		},
 		/// NDArray のある次元軸に関してイテレートします
 		Lanes {
 			mutable: bool,
@@ -307,7 +463,8 @@ mod typedef {
 		#[doc="ブール値の論理積"] And,
 		#[doc="ブール値の論理和"] Or,
 		#[doc="最大値"] Max(Option<Type>),
-		#[doc="最小値"] Min(Option<Type>)
+		#[doc="最小値"] Min(Option<Type>),
+		#[doc="`Default::default()` を単位元とし、指定した関数で結合する"] Custom(Path)
 	}
 	pub type RO = ReductionOperator;
 
@@ -341,6 +498,14 @@ mod typedef {
 		pub execution: EM,
 		/// リダクションの指定
 		pub reduction: RM,
+		/// 逆順にイテレートするかどうか
+		pub rev: bool,
+		/// 並列実行で直列に処理する要素数の下限
+		pub min_len: Option<Expr>,
+		/// 並列実行で直列に処理する要素数の上限
+		pub max_len: Option<Expr>,
+		/// 並列実行に使用するスレッドプール
+		pub pool: Option<Expr>,
 		/// 実行内容の本体
 		pub body: TS,
 		/// イテレータのリスト
@@ -371,6 +536,10 @@ mod typedef {
 		pub use_parallel_iterator: bool,
 		/// `use rayon::iter::IndexedParallelIterator;` を追加するフラグ
 		pub use_indexed_parallel_iterator: bool,
+		/// `use rayon::iter::IntoParallelRefIterator;` を追加するフラグ
+		pub use_into_parallel_ref_iterator: bool,
+		/// `use rayon::iter::IntoParallelRefMutIterator;` を追加するフラグ
+		pub use_into_parallel_ref_mut_iterator: bool,
 		/// `use ndarray::indices;` を追加するフラグ
 		pub use_ndarray_indices: bool
 	}
@@ -476,6 +645,10 @@ mod input {
 			let mut s = Self {
 				src: ts_string(&ts,exec),
 				debug: false,
+				rev: false,
+				min_len: None,
+				max_len: None,
+				pool: None,
 				args: vec![],
 				reduction: RM::None,
 				execution: exec,
@@ -502,7 +675,8 @@ mod input {
 				match &tt {
 					TT::Group(g) => {
 						match g.delimiter() {
-							TD::Parenthesis => {
+							// 関数名の直後でない括弧は `(x,(i,j)) = ...` のような変数のタプルとして扱う
+							TD::Parenthesis if matches!(current.last(),Some(TT::Ident(_))) => {
 								let p = FnParse::parse(&current,g.stream())
 								.unwrap_or_else(|| {
 									let mut ts = TS::from_iter(current.to_vec());
@@ -518,6 +692,7 @@ mod input {
 								self.body = Some(g.stream());
 								continue;
 							},
+							TD::Parenthesis => {},
 							_ => {
 								panic!("パースに失敗しました: {}",g.to_string());
 							}
@@ -666,6 +841,91 @@ mod input {
 
 				Some(())
 			})
+			// ($var,($index1,$index2,...)) = cell_and_index($array)
+			// ($var,$index) = cell_and_index($array)
+			.or_else(|| {
+				if p.vars.is_empty() { return None; }
+				if p.name!="cell_and_index" { return None; }
+
+				let et = parse2::<ExprTuple>(p.vars.clone()).ok()?;
+				if et.elems.len()!=2 { return None; }
+				let var = expr_path_to_ident(unwrap_enum!( Expr::Path = &et.elems[0] ))?;
+				let index = match &et.elems[1] {
+					Expr::Path(ep) => expr_path_to_ident(ep)?.to_token_stream(),
+					Expr::Tuple(t) => {
+						let idents = t.elems.iter()
+						.try_map_collect(|e| {
+							let ep = unwrap_enum!( Expr::Path = e );
+							expr_path_to_ident(ep)
+						})?;
+						let joined = idents.comma_join();
+						quote!( (#joined) )
+					},
+					_ => { return None; }
+				};
+				let array = parse2::<Expr>(p.args.clone()).ok()?;
+
+				self.args.push(
+					Arg::CellAndIndex { var, index, array }
+				);
+
+				Some(())
+			})
+			// ($key,$value) = entries($map)
+			// ($key,$value) = entries(mut $map)
+			// $var = entries($map)
+			// $var = mut entries($map)
+			.or_else(|| {
+				if p.vars.is_empty() { return None; }
+				let mut mutable = match &p.name[..] {
+					"entries" => false,
+					"mut entries" => true,
+					_ => { return None; }
+				};
+
+				let cmf = check_mut_flag(p.args.clone());
+				if cmf.0 && !mutable { mutable = true; }
+				else if cmf.0 && mutable { return None; }
+
+				let pattern = match parse2::<Ident>(p.vars.clone()) {
+					Ok(i) => i.to_token_stream(),
+					Err(_) => {
+						let et = parse2::<ExprTuple>(p.vars.clone()).ok()?;
+						if et.elems.len()!=2 { return None; }
+						let kv = et.elems.iter()
+						.try_map_collect(|e| {
+							let ep = unwrap_enum!( Expr::Path = e );
+							expr_path_to_ident(ep)
+						})?;
+						let joined = kv.comma_join();
+						quote!( (#joined) )
+					}
+				};
+				let map = parse2::<Expr>(cmf.1).ok()?;
+
+				self.args.push(
+					Arg::Entries { mutable, pattern, map }
+				);
+
+				Some(())
+			})
+			// $var = each_window($array,$size)
+			.or_else(|| {
+				if p.vars.is_empty() { return None; }
+				if p.name!="each_window" { return None; }
+
+				let var = parse2::<Ident>(p.vars.clone()).ok()?;
+				let args = split_ts(p.args.clone());
+				if args.len()!=2 { return None; }
+				let array = parse2::<Expr>(args[0].clone()).ok()?;
+				let size = parse2::<Expr>(args[1].clone()).ok()?;
+
+				self.args.push(
+					Arg::EachWindow { var, array, size }
+				);
+
+				Some(())
+			})
 			// reduce($op:$var)
 			// fold($op:$var)
 			// $inner = reduce($op:$outer)
@@ -727,6 +987,41 @@ mod input {
 
 				Some(())
 			})
+			// inner_serial()
+			.or_else(|| {
+				if !p.vars.is_empty() { return None; }
+				if !p.args.is_empty() { return None; }
+				if p.name!="inner_serial" { return None; }
+
+				self.execution = EM::Serial;
+
+				Some(())
+			})
+			// rev()
+			.or_else(|| {
+				if !p.vars.is_empty() { return None; }
+				if !p.args.is_empty() { return None; }
+				if p.name!="rev" { return None; }
+
+				self.rev = true;
+
+				Some(())
+			})
+			// with_min_len($n)
+			// with_max_len($n)
+			// pool($pool)
+			.or_else(|| {
+				if !p.vars.is_empty() { return None; }
+				let target = match &p.name[..] {
+					"with_min_len" => &mut self.min_len,
+					"with_max_len" => &mut self.max_len,
+					"pool" => &mut self.pool,
+					_ => { return None; }
+				};
+				*target = Some(parse2::<Expr>(p.args.clone()).ok()?);
+
+				Some(())
+			})
 			// debug()
 			.or_else(|| {
 				if !p.vars.is_empty() { return None; }
@@ -877,8 +1172,14 @@ mod input {
 		if tokens<3 { return None; }
 		let mut iter = ts.into_iter();
 		let mut captured = TS::new();
+		// パスに含まれる `::` では区切らない
+		let mut joint_colon = false;
 		while let Some(tt) = iter.next() {
-			if tt.to_string()==":" { break }
+			if let TT::Punct(p) = &tt {
+				if p.as_char()==':' && !joint_colon && p.spacing()==Spacing::Alone { break }
+				joint_colon = p.as_char()==':' && p.spacing()==Spacing::Joint;
+			}
+			else { joint_colon = false; }
 			captured = quote!( #captured #tt );
 		}
 		let op = reduction_op(captured)?;
@@ -888,6 +1189,14 @@ mod input {
 
 	/// リダクションの演算子を判定する
 	fn reduction_op(ts:TS) -> Option<RO> {
+		// combine = $path
+		let mut iter = ts.clone().into_iter();
+		if let (Some(TT::Ident(i)),Some(TT::Punct(p))) = (iter.next(),iter.next()) {
+			if i=="combine" && p.as_char()=='=' {
+				return Some(RO::Custom(parse2::<Path>(TS::from_iter(iter)).ok()?));
+			}
+		}
+
 		let mut ops = TS::new();
 		let mut t:Option<Type> = None;
 		for tt in ts {
@@ -963,11 +1272,16 @@ mod switcher {
 			let b = cond_bool_concat(&self.par_cond_bool);
 			let c = cond_cfg_concat(&self.par_cond_cfg);
 
-			let p = Converted::new(&self,self.execution).construct_whole();
-			let s = Converted::new(&self,EM::Serial).construct_whole();
+			// ループ本体のクロージャは並列/直列で共通なので、ローカルなマクロとして1度だけ出力し、各分岐からはそのマクロを呼び出す
+			let pc = Converted::new(&self,self.execution);
+			let sc = Converted::new(&self,EM::Serial);
+			let closure = pc.make_closure();
+			let call = quote!( for_each_body!() );
+			let p = pc.construct_with_closure(call.clone());
+			let s = sc.construct_with_closure(call);
 
 			// `par_cond_bool` や `par_cond_cfg` の指定のされ方に合わせて条件分岐する
-			match (self.par_cond_bool.len(),self.par_cond_cfg.len()) {
+			let switched = match (self.par_cond_bool.len(),self.par_cond_cfg.len()) {
 				(0,0) => { unreachable!() },
 				(_,0) => {
 					quote!(
@@ -990,7 +1304,12 @@ mod switcher {
 						#[cfg(not(#c))] { #s }
 					)
 				}
-			}
+			};
+
+			quote!( {
+				macro_rules! for_each_body { () => { #closure } }
+				#switched
+			} )
 		}
 
 	}
@@ -1027,6 +1346,127 @@ mod switcher {
 		quote!( all( #src ) )
 	}
 
+	#[cfg(test)]
+	#[test]
+	fn test_inner_serial() {
+		let src = quote!( i = index(10) { f(i); } );
+		let parallel = Input::new(src.clone(),EM::Parallel).construct().to_string();
+		assert!( parallel.contains("into_par_iter") );
+
+		let src = quote!( i = index(10) inner_serial() { f(i); } );
+		let serial = Input::new(src,EM::Parallel).construct().to_string();
+		assert!( !serial.contains("into_par_iter") );
+		assert!( !serial.contains("cfg") );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_body_emitted_once() {
+		let body = quote!( large_body(i); ).to_string();
+
+		// `par_cond_cfg` のみ (既定の `not(disable_parallel_execution)`)
+		let src = quote!( i = index(10) { large_body(i); } );
+		let generated = Input::new(src,EM::Parallel).construct().to_string();
+		assert_eq!( generated.matches(&body).count(), 1 );
+
+		// `par_cond_bool` と `par_cond_cfg` の両方
+		let src = quote!( i = index(10) s = reduce(+:sum) par_cond_bool(n>100) { large_body(i); } );
+		let generated = Input::new(src,EM::Parallel).construct().to_string();
+		assert_eq!( generated.matches(&body).count(), 1 );
+		assert_eq!( generated.matches(&quote!( for_each_body!() ).to_string()).count(), 3 );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_each_window() {
+		let src = quote!( w = each_window(a,3) { sum += w.iter().sum::<i32>(); } );
+		let serial = Input::new(src,EM::Serial).construct().to_string();
+		assert!( serial.contains(&quote!( (a).windows(3) ).to_string()) );
+
+		let src = quote!( w = each_window(a,3) { f(w); } );
+		let parallel = std::panic::catch_unwind(|| {
+			Input::new(src,EM::Parallel).construct().to_string()
+		});
+		assert!( parallel.is_err() );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_rev() {
+		let src = quote!( i = index(10) x = each(a) rev() { f(i,x); } );
+		let serial = Input::new(src,EM::Serial).construct().to_string();
+		assert!( serial.contains(&quote!( ( 0..(10) ).zip((a).iter()).rev().for_each ).to_string()) );
+
+		let src = quote!( i = index(10) rev() inner_serial() { f(i); } );
+		let serial = Input::new(src,EM::Parallel).construct().to_string();
+		assert!( serial.contains(&quote!( .rev() ).to_string()) );
+
+		let src = quote!( i = index(10) rev() { f(i); } );
+		let parallel = std::panic::catch_unwind(|| {
+			Input::new(src,EM::Parallel).construct().to_string()
+		});
+		assert!( parallel.is_err() );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_cell_and_index() {
+		let src = quote!( (x,(i,j)) = cell_and_index(a) { f(x,i,j); } );
+		let serial = Input::new(src,EM::Serial).construct().to_string();
+		assert!( serial.contains(&quote!( use ndarray::indices; ).to_string()) );
+		assert!( serial.contains(&quote!( (a).iter().zip(indices((a).raw_dim())).for_each ).to_string()) );
+		assert!( serial.contains(&quote!( |(x,(i,j))| ).to_string()) );
+
+		let src = quote!( (x,idx) = cell_and_index(a) { f(x,idx); } );
+		let parallel = Input::new(src,EM::Parallel).construct().to_string();
+		assert!( parallel.contains(&quote!( (a).as_slice().unwrap().into_par_iter().zip_eq ).to_string()) );
+		assert!( parallel.contains(&quote!( |(x,idx)| ).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_entries() {
+		let src = quote!( (k,v) = entries(map) { f(k,v); } );
+		let serial = Input::new(src,EM::Serial).construct().to_string();
+		assert!( serial.contains(&quote!( (map).iter().for_each ).to_string()) );
+		assert!( serial.contains(&quote!( |(k,v)| ).to_string()) );
+
+		let src = quote!( kv = entries(mut map) { g(kv); } );
+		let parallel = Input::new(src,EM::Parallel).construct().to_string();
+		assert!( parallel.contains(&quote!( use rayon::iter::IntoParallelRefMutIterator; ).to_string()) );
+		assert!( parallel.contains(&quote!( (map).par_iter_mut() ).to_string()) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_granularity_and_pool() {
+		let src = quote!( i = index(n) with_min_len(64) with_max_len(1024) pool(p) { f(i); } );
+		let parallel = Input::new(src.clone(),EM::Parallel).construct().to_string();
+		assert!( parallel.contains(&quote!( use rayon::iter::IndexedParallelIterator; ).to_string()) );
+		assert!( parallel.contains(&quote!( .with_min_len(64).with_max_len(1024).for_each ).to_string()) );
+		assert!( parallel.contains(&quote!( (p).install ).to_string()) );
+
+		let bridge = Input::new(src.clone(),EM::ParallelBridge).construct().to_string();
+		assert!( !bridge.contains("with_min_len") );
+		assert!( bridge.contains(&quote!( (p).install ).to_string()) );
+
+		let serial = Input::new(src,EM::Serial).construct().to_string();
+		assert!( !serial.contains("with_min_len") && !serial.contains("install") );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_custom_reduction() {
+		let src = quote!( x = each(a) fold(combine = stats::merge : acc) { acc.push(*x); } );
+		let parallel = Input::new(src,EM::Parallel).construct().to_string();
+		assert!( parallel.contains(&quote!( default_for(stats::merge) ).to_string()) );
+		assert!( parallel.contains(&quote!( stats::merge(a1_acc,a2_acc) ).to_string()) );
+		assert!( parallel.contains(&quote!( acc = stats::merge(::std::mem::take(&mut acc),tmp_acc); ).to_string()) );
+
+		let src = quote!( x = each(a) reduce(+(f64):s) { s = *x; } );
+		assert!( Input::new(src,EM::Serial).construct().to_string().contains("zero :: < f64 > ()") );
+	}
+
 }
 
 
@@ -1039,8 +1479,15 @@ mod converted {
 
 		/// 構造体の生成して、各 `Arg` ごとに構築 (`make_element` に丸投げ)
 		pub fn new(input:&Input,exec:EM) -> Self {
+			if input.rev && exec!=EM::Serial {
+				panic!("rev() は並列実行に対応していません");
+			}
 			let mut s = Self {
 				execution: exec,
+				rev: input.rev,
+				min_len: input.min_len.clone(),
+				max_len: input.max_len.clone(),
+				pool: input.pool.clone(),
 				reduction: input.reduction,
 				body: TS::from(input.body.as_ref().unwrap().clone()),
 				iterators: vec![],
@@ -1057,6 +1504,8 @@ mod converted {
 				use_parallel_bridge: false,
 				use_parallel_iterator: false,
 				use_indexed_parallel_iterator: false,
+				use_into_parallel_ref_iterator: false,
+				use_into_parallel_ref_mut_iterator: false,
 				use_ndarray_indices: false,
 			};
 			for arg in input.args.iter() {
@@ -1135,6 +1584,46 @@ mod converted {
 					self.iterators.push(iter);
 					self.lambda_args.push(la);
 				},
+				Arg::CellAndIndex {var,index,array} => {
+					self.use_ndarray_indices = true;
+					let indices = quote!( indices((#array).raw_dim()) );
+					let iter = match self.execution {
+						EM::Parallel => {
+							self.use_into_parallel_iterator = true;
+							self.use_indexed_parallel_iterator = true;
+							quote!( (#array).as_slice().unwrap().into_par_iter().zip_eq(#indices.into_iter().collect::<Vec<_>>()) )
+						},
+						_ => quote!( (#array).iter().zip(#indices) )
+					};
+
+					self.iterators.push(iter);
+					self.lambda_args.push(quote!( (#var,#index) ));
+				},
+				Arg::Entries {mutable,pattern,map} => {
+					let iter = match (self.execution,mutable) {
+						(EM::Parallel,false) => {
+							self.use_into_parallel_ref_iterator = true;
+							quote!( (#map).par_iter() )
+						},
+						(EM::Parallel,true) => {
+							self.use_into_parallel_ref_mut_iterator = true;
+							quote!( (#map).par_iter_mut() )
+						},
+						(_,false) => quote!( (#map).iter() ),
+						(_,true) => quote!( (#map).iter_mut() )
+					};
+
+					self.iterators.push(iter);
+					self.lambda_args.push(pattern.clone());
+				},
+				Arg::EachWindow {var,array,size} => {
+					if self.execution!=EM::Serial {
+						panic!("each_window は並列実行に対応していません");
+					}
+
+					self.iterators.push(quote!( (#array).windows(#size) ));
+					self.lambda_args.push(var.to_token_stream());
+				},
 				Arg::Reduction {assignment,operator,var_inside,var_outside} => {
 					let id = reduction_identity(operator);
 					let rfa1 = make_rfa_var1(var_inside);
@@ -1207,7 +1696,12 @@ mod converted {
 			RO::BitOr(None)|RO::BitXor(None) => quote!( zero() ),
 			RO::BitOr(Some(t))|RO::BitXor(Some(t)) => quote!( zero::<#t>() ),
 			RO::And => quote!( true ),
-			RO::Or => quote!( false )
+			RO::Or => quote!( false ),
+			// 単位元の型を結合関数の型から推論させる
+			RO::Custom(f) => quote!( {
+				fn default_for<T: ::std::default::Default>(_:fn(T,T)->T) -> T { T::default() }
+				default_for(#f)
+			} )
 		}
 	}
 
@@ -1223,7 +1717,8 @@ mod converted {
 			RO::Or        => quote!( #a1 || #a2 ),
 			RO::BitAnd(_) => quote!( #a1 & #a2 ),
 			RO::BitOr(_)  => quote!( #a1 | #a2 ),
-			RO::BitXor(_) => quote!( #a1 ^ #a2 )
+			RO::BitXor(_) => quote!( #a1 ^ #a2 ),
+			RO::Custom(f) => quote!( #f(#a1,#a2) )
 		}
 	}
 
@@ -1239,7 +1734,8 @@ mod converted {
 			RO::Or        => quote!( #o.or_assign(#i); ),
 			RO::BitAnd(_) => quote!( #o &= #i; ),
 			RO::BitOr(_)  => quote!( #o |= #i; ),
-			RO::BitXor(_) => quote!( #o ^= #i; )
+			RO::BitXor(_) => quote!( #o ^= #i; ),
+			RO::Custom(f) => quote!( #o = #f(::std::mem::take(&mut #o),#i); )
 		}
 	}
 
@@ -1280,11 +1776,49 @@ mod construct {
 	impl Converted {
 
 		/// for_each ループ全体を構築する
-		pub fn construct_whole(mut self) -> TS {
-			let iter = self.make_iterator();
+		pub fn construct_whole(self) -> TS {
+			let closure = self.make_closure();
+			self.construct_with_closure(closure)
+		}
+
+		/// 各要素に対して実行するクロージャを生成する。実行モードに依らず同じ内容になる。
+		/// * リダクションがある場合、本体は即時に呼び出すクロージャで包む (本体の `return` で次の要素に進めるようにするため)。呼び出し元のクレートで clippy の警告が出ないよう `allow` を付ける。
+		pub fn make_closure(&self) -> TS {
 			let la = self.make_lambda_args();
 			let ad = &self.advance_defs;
 			let body = &self.body;
+			let id = self.reduction_identities.tuple();
+			let def = self.reduction_vars_inside.tuple_mut();
+			let ret = self.reduction_vars_inside.tuple();
+
+			match self.reduction {
+				RM::None => quote!(
+					|#la| { #ad #body }
+				),
+				RM::Fold => quote!(
+					|#la| {
+						#ad
+						#[allow(clippy::redundant_closure_call)]
+						(|| { #body })();
+						#ret
+					}
+				),
+				RM::Reduce => quote!(
+					|#la| {
+						#ad
+						#[allow(unused_mut,unused_assignments)]
+						let #def = #id;
+						#[allow(clippy::redundant_closure_call)]
+						(|| { #body })();
+						#ret
+					}
+				)
+			}
+		}
+
+		/// 各要素に対して実行するクロージャ `closure` を受け取って、 for_each ループ全体を構築する
+		pub fn construct_with_closure(mut self,closure:TS) -> TS {
+			let iter = self.make_iterator();
 
 			if !matches!(self.execution,EM::Serial) { self.use_parallel_iterator = true; }
 
@@ -1292,7 +1826,7 @@ mod construct {
 			if matches!(self.reduction,RM::None) {
 				quote!(
 					#iter.for_each(
-						|#la| { #ad #body }
+						#closure
 					)
 				)
 			}
@@ -1300,8 +1834,6 @@ mod construct {
 				let id = self.reduction_identities.tuple();
 				let oa = &self.reduction_outside_assignment;
 				let tv = self.reduction_vars_outside.tuple();
-				let def = self.reduction_vars_inside.tuple_mut();
-				let ret = self.reduction_vars_inside.tuple();
 				let reduction = self.make_reduction_func();
 
 				match (self.reduction,self.execution) {
@@ -1310,11 +1842,7 @@ mod construct {
 							let #tv =
 							#iter.fold(
 								|| #id,
-								|#la| {
-									#ad
-									(|| { #body })();
-									#ret
-								}
+								#closure
 							)
 							.reduce(
 								|| #id,
@@ -1328,11 +1856,7 @@ mod construct {
 							let #tv =
 							#iter.fold(
 								#id,
-								|#la| {
-									#ad
-									(|| { #body })();
-									#ret
-								}
+								#closure
 							);
 							#oa
 						)
@@ -1341,13 +1865,7 @@ mod construct {
 						quote!(
 							let #tv =
 							#iter.map(
-								|#la| {
-									#ad
-									#[allow(unused_mut,unused_assignments)]
-									let #def = #id;
-									(|| { #body })();
-									#ret
-								}
+								#closure
 							)
 							.reduce(
 								|| #id,
@@ -1360,13 +1878,7 @@ mod construct {
 						quote!(
 							let #tv =
 							#iter.map(
-								|#la| {
-									#ad
-									#[allow(unused_mut,unused_assignments)]
-									let #def = #id;
-									(|| { #body })();
-									#ret
-								}
+								#closure
 							)
 							.reduce(#reduction)
 							.unwrap();
@@ -1377,6 +1889,11 @@ mod construct {
 				}
 			};
 
+			// スレッドプールが指定された場合は、その中で並列処理を実行する
+			if let (Some(pool),false) = (&self.pool,matches!(self.execution,EM::Serial)) {
+				src = quote!( (#pool).install(|| { #src }); );
+			}
+
 			let import = self.make_import();
 			src = quote!( #import #src );
 
@@ -1404,6 +1921,21 @@ mod construct {
 				}
 			}
 
+			if self.rev {
+				zi = quote!( #zi.rev() );
+			}
+
+			if matches!(self.execution,EM::Parallel) {
+				if let Some(n) = &self.min_len {
+					self.use_indexed_parallel_iterator = true;
+					zi = quote!( #zi.with_min_len(#n) );
+				}
+				if let Some(n) = &self.max_len {
+					self.use_indexed_parallel_iterator = true;
+					zi = quote!( #zi.with_max_len(#n) );
+				}
+			}
+
 			if matches!(self.execution,EM::ParallelBridge) {
 				self.use_parallel_bridge = true;
 				zi = quote!( #zi.par_bridge() );
@@ -1457,6 +1989,10 @@ mod construct {
 					rayon::iter::ParallelIterator,
 				use_indexed_parallel_iterator ->
 					rayon::iter::IndexedParallelIterator,
+				use_into_parallel_ref_iterator ->
+					rayon::iter::IntoParallelRefIterator,
+				use_into_parallel_ref_mut_iterator ->
+					rayon::iter::IntoParallelRefMutIterator,
 				use_ndarray_indices ->
 					ndarray::indices,
 			);
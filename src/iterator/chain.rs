@@ -1,3 +1,5 @@
+use super::*;
+
 /// イテレータのタプルに対してチェーンを定義するモジュール
 pub mod for_iters_tuple {
 
@@ -16,6 +18,21 @@ pub mod for_iters_tuple {
 		pub(crate) current_back: usize
 	}
 
+	/// 複数のイテレータのタプルを交互に取り出すイテレータに変換するトレイト
+	pub trait IntoInterleave: Sized {
+		/// イテレータのタプル `(I1,I2,I3,...)` から `I1`→`I2`→`I3`→`I1`→... という順に1つずつ要素を取り出すイテレータに変換します。空になったイテレータは読み飛ばし、全てが空になるまで続けます。
+		fn interleave(self) -> Interleave<Self>;
+	}
+
+	/// 複数のイテレータから順番に1つずつ要素を取り出すイテレータです
+	pub struct Interleave<T> {
+		pub(crate) iters_tuple: T,
+		/// 次に取り出すイテレータの位置
+		pub(crate) current: usize,
+		/// 既に空になったイテレータをビットで記録する
+		pub(crate) exhausted: u32
+	}
+
 	/// * 複数のイテレータに対する `Chain` トレイトを実装するマクロ
 	/// * `implement!( I0 0 I1 1 I2 2 ... I(N-1) (N-1) )` と指定すれば、 `N` 個の要素まで対応する
 	macro_rules! implement {
@@ -55,17 +72,68 @@ pub mod for_iters_tuple {
 				}
 
 				fn size_hint(&self) -> (usize, Option<usize>) {
+					// 前後から消費された分は各イテレータの size_hint に反映されているので、単純に和をとればよい
 					let size_hint = ( $( self.iters_tuple.$n.size_hint(),)+ );
-					let l = 0 $(+ size_hint.$n.0 )+;
+					let l = 0_usize $(.saturating_add(size_hint.$n.0) )+;
 					let u = ( $(size_hint.$n.1,)+ )
 					.zip_options()
-					.map(|t| 0 $(+ t.$n)+ );
+					.and_then(|t| Some(0) $(.and_then(|s:usize| s.checked_add(t.$n) ) )+ );
 					(l,u)
 				}
 			}
 
 			implement! {@backward $t 0 | $( $i $n )+ }
 
+			impl<$t,$($i),+> IntoInterleave for ($($i,)+)
+			where $( $i: Iterator<Item=$t> ),+
+			{
+				fn interleave(self) -> Interleave<Self> {
+					Interleave { iters_tuple: self, current: 0, exhausted: 0 }
+				}
+			}
+
+			impl<$t,$($i),+> Iterator for Interleave<($($i,)+)>
+			where $( $i: Iterator<Item=$t> ),+
+			{
+				type Item = $t;
+
+				fn next(&mut self) -> Option<Self::Item> {
+					let len = [$($n),+].len();
+					for _ in 0..len {
+						let k = self.current;
+						self.current = (k+1)%len;
+						if self.exhausted & (1<<k) != 0 { continue; }
+						let v = match k {
+							$( $n => self.iters_tuple.$n.next(), )+
+							_ => unreachable!()
+						};
+						if v.is_some() { return v; }
+						self.exhausted |= 1<<k;
+					}
+					None
+				}
+
+				fn size_hint(&self) -> (usize, Option<usize>) {
+					// 空になったイテレータは以降取り出さないので数えない
+					let size_hint = ( $(
+						if self.exhausted & (1<<$n) != 0 { (0,Some(0)) }
+						else { self.iters_tuple.$n.size_hint() },
+					)+ );
+					let l = 0_usize $(.saturating_add(size_hint.$n.0) )+;
+					let u = ( $(size_hint.$n.1,)+ )
+					.zip_options()
+					.and_then(|t| Some(0) $(.and_then(|s:usize| s.checked_add(t.$n) ) )+ );
+					(l,u)
+				}
+			}
+
+			impl<$t,$($i),+> ExactSizeIterator for Interleave<($($i,)+)>
+			where $( $i: ExactSizeIterator<Item=$t> ),+ {}
+
+			/// 一度 `None` を返したイテレータからは取り出さないので、各イテレータによらず `FusedIterator` となる
+			impl<$t,$($i),+> FusedIterator for Interleave<($($i,)+)>
+			where $( $i: Iterator<Item=$t> ),+ {}
+
 			impl<$t,$($i),+> ExactSizeIterator for Chain<($($i,)+)>
 			where $( $i: ExactSizeIterator<Item=$t> ),+ {}
 
@@ -95,16 +163,423 @@ pub mod for_iters_tuple {
 					} )+
 					None
 				}
+
+				// 先頭のイテレータまで読み飛ばした後の `by` は使われない
+				#[allow(unused_assignments)]
+				fn nth_back(&mut self,mut by:usize) -> Option<Self::Item> {
+					// 要素数から飛ばす位置を含むイテレータを特定し、それより後ろのイテレータは一度に読み飛ばす
+					$( if self.current_back==($n_largest-$n) {
+						let l = self.iters_tuple.$n.len();
+						if by<l { return self.iters_tuple.$n.nth_back(by); }
+						// 順方向から再び取り出されないように、読み飛ばしたイテレータは空にしておく
+						self.iters_tuple.$n.nth_back(l);
+						by -= l;
+						self.current_back += 1;
+					} )+
+					None
+				}
 			}
 		};
 	}
 	pub(crate) use implement;
 
+	#[cfg(test)]
+	#[test]
+	fn test_size_hint() {
+		use crate::prelude::*;
+
+		let expected = (0..3).chain(3..5).chain(5..9).collect::<Vec<_>>();
+		// 前後からの取り出し方を色々変えて、残りの要素数が常に一致することを確かめる
+		for pattern in 0_u32..(1<<expected.len()) {
+			let mut it = (0..3,3..5,5..9).into_chained_iter();
+			let mut head = vec![];
+			let mut tail = vec![];
+			for k in 0..expected.len() {
+				let v = if pattern & (1<<k) == 0 { it.next() } else { it.next_back() };
+				if pattern & (1<<k) == 0 { head.push(v.unwrap()); } else { tail.push(v.unwrap()); }
+				let rest = expected.len()-k-1;
+				assert_eq!( it.size_hint(), (rest,Some(rest)) );
+				assert_eq!( it.len(), rest );
+			}
+			assert_eq!( (it.next(),it.next_back()), (None,None) );
+			head.extend(tail.into_iter().rev());
+			assert_eq!( head, expected );
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_nth_back() {
+		use crate::prelude::*;
+
+		let chained = || (0..3,3..5,5..9).into_chained_iter();
+		for k in 0..10 {
+			let mut a = chained();
+			let mut b = chained();
+			let v = (0..=k).map(|_| b.next_back() ).last().unwrap();
+			assert_eq!( a.nth_back(k), v );
+			assert_eq!( a.len(), b.len() );
+			assert_eq!( a.collect::<Vec<_>>(), b.collect::<Vec<_>>() );
+		}
+
+		let mut it = chained();
+		assert_eq!( it.nth_back(5), Some(3) );
+		assert_eq!( it.nth_back(1), Some(1) );
+		assert_eq!( it.collect::<Vec<_>>(), vec![0] );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_interleave() {
+		use crate::prelude::*;
+
+		let v = (vec![1,2,3].into_iter(),vec![10,20].into_iter()).interleave().collect::<Vec<_>>();
+		assert_eq!( v, vec![1,10,2,20,3] );
+
+		let mut it = (0..1,10..14,std::iter::empty(),100..102).interleave();
+		assert_eq!( it.len(), 7 );
+		assert_eq!( (it.next(),it.next(),it.next()), (Some(0),Some(10),Some(100)) );
+		assert_eq!( it.size_hint(), (4,Some(4)) );
+		assert_eq!( it.collect::<Vec<_>>(), vec![11,101,12,13] );
+	}
+
+}
+
+
+
+/// 並列イテレータのタプルに対してチェーンを定義するモジュール
+#[cfg(feature="parallel")]
+pub mod for_parallel_iters {
+	use super::*;
+	use rayon_plumbing::*;
+
+	/// 複数の並列イテレータのタプルをチェーンした並列イテレータに変換するトレイト
+	pub trait IntoChain: Sized {
+		/// 並列イテレータのタプル `(I1,I2,I3,...)` を `I1`→`I2`→`I3` という順に連結した1つの並列イテレータに変換します
+		fn into_chained_iter(self) -> Chain<Self>;
+		/// 並列イテレータのタプル `(I1,I2,I3,...)` を `I1`→`I2`→`I3` という順に連結した1つの並列イテレータに変換します
+		fn chain(self) -> Chain<Self> { self.into_chained_iter() }
+	}
+
+	/// 複数の並列イテレータをチェーンする (連続に繋げる) 並列イテレータです
+	pub struct Chain<T> {
+		pub(crate) iters_tuple: T
+	}
+
+	/// 各並列イテレータのプロデューサを順に取り出し、連結していくコールバック
+	pub(crate) struct ChainCallback<CCB,A,R> {
+		pub(crate) child_callback: CCB,
+		/// これまでに連結したプロデューサを `(A,)` の形式で保持する。最初は `()`
+		pub(crate) producer: A,
+		/// これまでに連結したプロデューサの要素数
+		pub(crate) producer_len: usize,
+		/// 次に受け取るプロデューサの要素数
+		pub(crate) len: usize,
+		/// まだプロデューサを取り出していない並列イテレータのタプル
+		pub(crate) rest: R
+	}
+
+	/// 2つのプロデューサを連結したプロデューサ。3つ以上の場合は入れ子にする
+	pub(crate) struct ChainProducer<A,B> {
+		pub(crate) a: A,
+		pub(crate) a_len: usize,
+		pub(crate) b: B
+	}
+
+	impl<A,B,T> Producer for ChainProducer<A,B>
+	where A: Producer<Item=T>, B: Producer<Item=T>
+	{
+		type Item = T;
+		type IntoIter = super::for_iters_tuple::Chain<(A::IntoIter,B::IntoIter)>;
+
+		fn into_iter(self) -> Self::IntoIter {
+			use super::for_iters_tuple::IntoChain;
+			(self.a.into_iter(),self.b.into_iter()).into_chained_iter()
+		}
+
+		fn min_len(&self) -> usize {
+			self.a.min_len().max(self.b.min_len())
+		}
+
+		fn max_len(&self) -> usize {
+			self.a.max_len().min(self.b.max_len())
+		}
+
+		fn split_at(self, index: usize) -> (Self, Self) {
+			// 分割点が前側のプロデューサに含まれる場合は、後側のプロデューサを先頭で分割する
+			if index<=self.a_len {
+				let (a_left,a_right) = self.a.split_at(index);
+				let (b_left,b_right) = self.b.split_at(0);
+				(
+					Self { a: a_left, a_len: index, b: b_left },
+					Self { a: a_right, a_len: self.a_len-index, b: b_right }
+				)
+			}
+			// 分割点が後側のプロデューサに含まれる場合は、前側のプロデューサを末尾で分割する
+			else {
+				let (a_left,a_right) = self.a.split_at(self.a_len);
+				let (b_left,b_right) = self.b.split_at(index-self.a_len);
+				(
+					Self { a: a_left, a_len: self.a_len, b: b_left },
+					Self { a: a_right, a_len: 0, b: b_right }
+				)
+			}
+		}
+	}
+
+	/// * 複数の並列イテレータに対する `Chain` トレイトを実装するマクロ
+	/// * `implement!( I0 0 I1 1 I2 2 ... I(N-1) (N-1) )` と指定すれば、 `N` 個の要素まで対応する
+	macro_rules! implement {
+		( $( $i:ident $n:tt )+ ) => {
+			mod impl_chain_parallel_iters {
+				use super::*;
+				use crate::iterator::chain::for_parallel_iters::*;
+				use rayon_plumbing::*;
+
+				implement! {@each T | $( $i $n )+ }
+			}
+		};
+		(@each $t:ident $( $i:ident $n:tt )* | $in:ident $nn:tt $( $others:tt )* ) => {
+			implement! {@each $t $( $i $n )* | }
+			implement! {@each $t $( $i $n )* $in $nn | $($others)* }
+		};
+		(@each $t:ident $i0:ident $n0:tt $( $i:ident $n:tt )* | ) => {
+
+			impl<$t,$i0 $(,$i)*> IntoChain for ($i0, $($i,)*)
+			where $t: Send, $i0: IndexedParallelIterator<Item=$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				fn into_chained_iter(self) -> Chain<Self> {
+					Chain { iters_tuple: self }
+				}
+			}
+
+			impl<$t,$i0 $(,$i)*> ParallelIterator for Chain<($i0, $($i,)*)>
+			where $t: Send, $i0: IndexedParallelIterator<Item=$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				type Item = $t;
+
+				fn drive_unindexed<CC>(self, child_consumer: CC) -> CC::Result
+				where CC: UnindexedConsumer<Self::Item>
+				{ bridge(self,child_consumer) }
+
+				fn opt_len(&self) -> Option<usize> {
+					Some(self.len())
+				}
+			}
+
+			impl<$t,$i0 $(,$i)*> IndexedParallelIterator for Chain<($i0, $($i,)*)>
+			where $t: Send, $i0: IndexedParallelIterator<Item=$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				fn drive<CC>(self, child_consumer: CC) -> CC::Result
+				where CC: Consumer<Self::Item>
+				{ bridge(self,child_consumer) }
+
+				fn len(&self) -> usize {
+					self.iters_tuple.$n0.len() $(+ self.iters_tuple.$n.len() )*
+				}
+
+				fn with_producer<CCB>(self, child_callback: CCB) -> CCB::Output
+				where CCB: ProducerCallback<Self::Item>
+				{
+					let len = self.iters_tuple.$n0.len();
+					self.iters_tuple.$n0.with_producer(ChainCallback {
+						child_callback,
+						producer: (),
+						producer_len: 0,
+						len,
+						rest: ( $( self.iters_tuple.$n, )* )
+					})
+				}
+			}
+
+			implement! {@callback $t $i0 $n0 $( $i $n )* }
+
+		};
+		(@each $t:ident | ) => {
+			implement! {@callback $t }
+		};
+
+		// `ChainCallback` の実装: `rest` に残っている並列イテレータの個数ごとに実装する (インデクスは `rest` のタプルにおけるもの)
+		(@callback $t:ident $( $i:ident $n:tt )* ) => {
+
+			impl<CCB,$t $(,$i)*> ProducerCallback<$t> for ChainCallback<CCB,(),($($i,)*)>
+			where CCB: ProducerCallback<$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				type Output = CCB::Output;
+				fn callback<P>(self, producer: P) -> Self::Output
+				where P: Producer<Item=$t>
+				{
+					ChainCallback {
+						child_callback: self.child_callback,
+						producer: (producer,),
+						producer_len: self.len,
+						len: 0,
+						rest: self.rest
+					}.proceed()
+				}
+			}
+
+			impl<CCB,A,$t $(,$i)*> ProducerCallback<$t> for ChainCallback<CCB,(A,),($($i,)*)>
+			where CCB: ProducerCallback<$t>, A: Producer<Item=$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				type Output = CCB::Output;
+				fn callback<P>(self, producer: P) -> Self::Output
+				where P: Producer<Item=$t>
+				{
+					ChainCallback {
+						child_callback: self.child_callback,
+						producer: (ChainProducer {
+							a: self.producer.0,
+							a_len: self.producer_len,
+							b: producer
+						},),
+						producer_len: self.producer_len + self.len,
+						len: 0,
+						rest: self.rest
+					}.proceed()
+				}
+			}
+
+			impl<CCB,A,$t $(,$i)*> ChainCallback<CCB,(A,),($($i,)*)>
+			where CCB: ProducerCallback<$t>, A: Producer<Item=$t>, $( $i: IndexedParallelIterator<Item=$t> ),*
+			{
+				/// 次の並列イテレータからプロデューサを取り出す。全て取り出し終えていれば子のコールバックに渡す
+				fn proceed(self) -> CCB::Output {
+					implement! {@proceed self $( $i $n )* }
+				}
+			}
+
+		};
+		(@proceed $s:ident ) => {
+			$s.child_callback.callback($s.producer.0)
+		};
+		(@proceed $s:ident $i0:ident $n0:tt $( $i:ident $n:tt )* ) => {{
+			let len = $s.rest.$n0.len();
+			$s.rest.$n0.with_producer(ChainCallback {
+				child_callback: $s.child_callback,
+				producer: $s.producer,
+				producer_len: $s.producer_len,
+				len,
+				rest: ( $( $s.rest.$n, )* )
+			})
+		}};
+	}
+	pub(crate) use implement;
+
+	#[cfg(test)]
+	#[test]
+	fn test_parallel_chain() {
+		use crate::prelude::*;
+		use rayon::prelude::*;
+
+		let serial = ( vec![1,2,3].into_iter(), 4..6, vec![].into_iter(), vec![6,7,8,9].into_iter() )
+		.into_chained_iter()
+		.collect::<Vec<i32>>();
+		let parallel = ( vec![1,2,3].into_par_iter(), (4..6).into_par_iter(), vec![].into_par_iter(), vec![6,7,8,9].into_par_iter() )
+		.into_chained_iter();
+		assert_eq!( parallel.len(), serial.len() );
+		assert_eq!( parallel.with_max_len(1).collect::<Vec<i32>>(), serial );
+	}
+
+}
+
+
+
+/// イテレータの配列に対してチェーンを定義するモジュール
+pub mod for_iters_array {
+	use super::*;
+
+	/// 複数のイテレータの配列をチェーンしたイテレータに変換するトレイト
+	pub trait IntoChain<I> {
+		/// イテレータの配列 `[I;N]` や `Vec<I>` などを、先頭から順に連結した1つのイテレータに変換します
+		fn into_chained_iter(self) -> Chain<I>;
+	}
+	impl<II,I,T> IntoChain<I> for II
+	where II: IntoIterator<Item=I>, I: Iterator<Item=T>
+	{
+		fn into_chained_iter(self) -> Chain<I> {
+			let iters = self.into_iter().collect::<Vec<_>>();
+			Chain { current: 0, current_back: iters.len(), iters }
+		}
+	}
+
+	/// 複数のイテレータの配列をチェーンする (連続に繋げる) イテレータです
+	pub struct Chain<I> {
+		iters: Vec<I>,
+		/// 順方向で現在イテレートしているイテレータの位置
+		current: usize,
+		/// 逆方向で現在イテレートしているイテレータの次の位置
+		current_back: usize
+	}
+
+	impl<I,T> Iterator for Chain<I>
+	where I: Iterator<Item=T>
+	{
+		type Item = T;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			while self.current<self.current_back {
+				if let s @ Some(_) = self.iters[self.current].next() { return s; }
+				self.current += 1;
+			}
+			None
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.iters[self.current..self.current_back].iter()
+			.map(|i| i.size_hint() )
+			.fold((0,Some(0)),|(l1,u1),(l2,u2)| (
+				l1.saturating_add(l2),
+				u1.zip(u2).and_then(|(u1,u2)| u1.checked_add(u2) )
+			) )
+		}
+	}
+
+	impl<I,T> DoubleEndedIterator for Chain<I>
+	where I: DoubleEndedIterator<Item=T>
+	{
+		fn next_back(&mut self) -> Option<Self::Item> {
+			while self.current<self.current_back {
+				if let s @ Some(_) = self.iters[self.current_back-1].next_back() { return s; }
+				self.current_back -= 1;
+			}
+			None
+		}
+	}
+
+	impl<I,T> ExactSizeIterator for Chain<I>
+	where I: ExactSizeIterator<Item=T> {}
+
+	/// 一度 `None` を返したイテレータには戻らないので、各イテレータによらず `FusedIterator` となる
+	impl<I,T> FusedIterator for Chain<I>
+	where I: Iterator<Item=T> {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_chain_array() {
+		let chained = || vec![0..3,3..3,3..5,5..9].into_chained_iter();
+		assert_eq!( chained().len(), 9 );
+		assert_eq!( chained().collect::<Vec<_>>(), (0..9).collect::<Vec<_>>() );
+		assert_eq!( chained().rev().collect::<Vec<_>>(), (0..9).rev().collect::<Vec<_>>() );
+
+		let mut it = chained();
+		assert_eq!( (it.next(),it.next_back(),it.next_back()), (Some(0),Some(8),Some(7)) );
+		assert_eq!( it.len(), 6 );
+		assert_eq!( it.collect::<Vec<_>>(), (1..7).collect::<Vec<_>>() );
+		assert_eq!( Vec::<std::ops::Range<i32>>::new().into_chained_iter().next(), None );
+	}
+
 }
 
 
 
 /// このモジュールからクレートの `prelude` でアクセスできるようにするアイテムをまとめたもの
 pub(crate) mod for_prelude {
-	pub use super::for_iters_tuple::IntoChain as IntoChainedIteratorForIteratorsTuple;
+	pub use super::for_iters_tuple::{
+		IntoChain as IntoChainedIteratorForIteratorsTuple,
+		IntoInterleave as IntoInterleavedIteratorForIteratorsTuple
+	};
+	pub use super::for_iters_array::IntoChain as IntoArrayChainedIterator;
+	#[cfg(feature="parallel")]
+	pub use super::for_parallel_iters::IntoChain as IntoChainedIteratorForParallelIteratorsTuple;
 }
@@ -781,8 +781,189 @@ mod iter_impl {
 					where_parallel: { F: Fn(E) -> Result<T,G> + Send + Sync }
 					call: { self,input -> input.or_else(|i| self.0(i) ) }
 				}
+				{
+					name_fn: ok_or
+					name_iter_serial: OkOr
+					name_iter_parallel: ParallelOkOr
+					name_map_fn: OkOrFn
+					desc: "論理和をとる。入力の `Result<T,E>` 型が `Ok` の場合はそのまま出力され、 `Err` の場合はイテレータに与えられた `Result<T,G>` 型の値を複製して出力する。"
+					params: [ default:R ]
+					phantom_params: [ G ]
+					type_params: [ G, R ]
+					output_type: { Result<T,G> }
+					where_serial: { R: Clone + Into<Result<T,G>> }
+					where_parallel: { R: Clone + Into<Result<T,G>> + Send + Sync, G: Send + Sync }
+					call: { self,input -> input.or_else(|_| self.0.clone().into() ) }
+				}
+				{
+					name_fn: map_ok_into
+					name_iter_serial: MapOkInto
+					name_iter_parallel: ParallelMapOkInto
+					name_map_fn: MapOkIntoFn
+					desc: "`Into` トレイトに依拠して `Result<T,E>` 型の `Ok` の部分の値 `T` を `U` に変換して `Result<U,E>` にする。 `Err` の場合はそのまま返される。"
+					phantom_params: [ U ]
+					type_params: [ U ]
+					output_type: { Result<U,E> }
+					where_serial: { T: Into<U> }
+					where_parallel: { T: Into<U>, U: Send + Sync }
+					call: { self,input -> input.map(|i| i.into() ) }
+				}
+				{
+					name_fn: inspect_ok
+					name_iter_serial: InspectOk
+					name_iter_parallel: ParallelInspectOk
+					name_map_fn: InspectOkFn
+					desc: "`Result<T,E>` 型が `Ok` の場合に内包データの参照をクロージャに渡す。入力はそのまま出力される。"
+					params: [ f:F ]
+					type_params: [ F ]
+					output_type: { Result<T,E> }
+					where_serial: { F: FnMut(&T) }
+					where_parallel: { F: Fn(&T) + Send + Sync }
+					call: { self,input -> { if let Ok(i) = &input { self.0(i); } input } }
+				}
+				{
+					name_fn: inspect_err
+					name_iter_serial: InspectErr
+					name_iter_parallel: ParallelInspectErr
+					name_map_fn: InspectErrFn
+					desc: "`Result<T,E>` 型が `Err` の場合に内包データの参照をクロージャに渡す。入力はそのまま出力される。"
+					params: [ f:F ]
+					type_params: [ F ]
+					output_type: { Result<T,E> }
+					where_serial: { F: FnMut(&E) }
+					where_parallel: { F: Fn(&E) + Send + Sync }
+					call: { self,input -> { if let Err(i) = &input { self.0(i); } input } }
+				}
 			]
 		}
+
+		/// `Result<T,E>` のイテレータを集約する終端のメソッドを提供するトレイト
+		pub trait TryCollect<T,E>: Iterator<Item=Result<T,E>> + Sized {
+			/// `Ok` の値を `C` に集約して `Ok(C)` を返す。 `Err` が現れた時点で走査を打ち切り、その `Err` を返す。
+			fn try_collect<C: FromIterator<T>>(self) -> Result<C,E> {
+				self.collect()
+			}
+		}
+		impl<I,T,E> TryCollect<T,E> for I where I: Iterator<Item=Result<T,E>> {}
+
+		/// `Result<T,E>` のイテレータを `Ok` と `Err` に振り分ける終端のメソッドを提供するトレイト
+		pub trait PartitionResults<T,E>: Iterator<Item=Result<T,E>> + Sized {
+			/// イテレータを1回走査し、 `Ok` の値を1つ目の `Vec` に、 `Err` の値を2つ目の `Vec` に順に格納して返す。
+			fn partition_results(self) -> (Vec<T>,Vec<E>) {
+				let mut oks = Vec::new();
+				let mut errs = Vec::new();
+				for r in self {
+					match r {
+						Ok(v) => oks.push(v),
+						Err(e) => errs.push(e)
+					}
+				}
+				(oks,errs)
+			}
+		}
+		impl<I,T,E> PartitionResults<T,E> for I where I: Iterator<Item=Result<T,E>> {}
+
+		#[cfg(feature="parallel")]
+		/// `Result<T,E>` の並列イテレータを `Ok` と `Err` に振り分ける終端のメソッドを提供するトレイト
+		pub trait ParallelPartitionResults<T: Send,E: Send>: ParallelIterator<Item=Result<T,E>> {
+			/// 並列イテレータを走査し、 `Ok` の値を1つ目の `Vec` に、 `Err` の値を2つ目の `Vec` に格納して返す。
+			/// * 各スレッドで `fold` により振り分けた結果を `reduce` で結合する。元の並列イテレータの順序は保たれる。
+			fn partition_results(self) -> (Vec<T>,Vec<E>) {
+				self
+				.fold(
+					|| (Vec::new(),Vec::new()),
+					|(mut oks,mut errs),r| {
+						match r {
+							Ok(v) => oks.push(v),
+							Err(e) => errs.push(e)
+						}
+						(oks,errs)
+					}
+				)
+				.reduce(
+					|| (Vec::new(),Vec::new()),
+					|(mut oks1,mut errs1),(oks2,errs2)| {
+						oks1.extend(oks2);
+						errs1.extend(errs2);
+						(oks1,errs1)
+					}
+				)
+			}
+		}
+		#[cfg(feature="parallel")]
+		impl<I,T: Send,E: Send> ParallelPartitionResults<T,E> for I where I: ParallelIterator<Item=Result<T,E>> {}
+
+		#[cfg(test)]
+		#[test]
+		fn test_partition_results() {
+			let v: Vec<Result<i32,&str>> = vec![];
+			assert_eq!( v.clone().into_iter().partition_results(), (vec![],vec![]) );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().partition_results(), (vec![],vec![]) );
+
+			let v: Vec<Result<i32,String>> = (0..1000).map(|i| if i%3==0 { Err(i.to_string()) } else { Ok(i) } ).collect();
+			let expected = (
+				(0..1000).filter(|i| i%3!=0 ).collect::<Vec<_>>(),
+				(0..1000).filter(|i| i%3==0 ).map(|i| i.to_string() ).collect::<Vec<_>>()
+			);
+			assert_eq!( v.clone().into_iter().partition_results(), expected );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().partition_results(), expected );
+		}
+
+		#[cfg(test)]
+		#[test]
+		fn test_try_collect() {
+			use super::for_option::TryCollectOption;
+
+			let v: Vec<Result<i32,&str>> = vec![Ok(1),Ok(2),Ok(3)];
+			assert_eq!( v.into_iter().map_ok(|i| i*2 ).try_collect::<Vec<_>>(), Ok(vec![2,4,6]) );
+
+			let mut visited = 0;
+			let v: Vec<Result<i32,&str>> = vec![Ok(1),Err("a"),Ok(3),Err("b")];
+			let r = v.into_iter().inspect_ok(|_| visited+=1 ).try_collect::<Vec<_>>();
+			assert_eq!( (r,visited), (Err("a"),1) );
+
+			assert_eq!( vec![Some(1),Some(2)].into_iter().try_collect_option::<Vec<_>>(), Some(vec![1,2]) );
+			assert_eq!( vec![Some(1),None,Some(3)].into_iter().try_collect_option::<Vec<_>>(), None );
+		}
+
+		#[cfg(test)]
+		#[test]
+		fn test_inspect() {
+			use super::for_option::IntoMap as _;
+			let v: Vec<Result<i32,i32>> = vec![Ok(1),Err(2),Ok(3)];
+			let (mut oks,mut errs) = (vec![],vec![]);
+			let r: Vec<_> = v.clone().into_iter().inspect_ok(|i| oks.push(*i) ).inspect_err(|e| errs.push(*e) ).collect();
+			assert_eq!( (r,oks,errs), (v,vec![1,3],vec![2]) );
+
+			let v = vec![Some(1),None,Some(3)];
+			let mut somes = vec![];
+			let r: Vec<_> = v.clone().into_iter().inspect_some(|i| somes.push(*i) ).collect();
+			assert_eq!( (r,somes), (v,vec![1,3]) );
+		}
+
+		#[cfg(test)]
+		#[test]
+		fn test_ok_or() {
+			let v: Vec<Result<i32,&str>> = vec![Ok(1),Err("e"),Ok(3)];
+			let expected: Vec<Result<i32,u8>> = vec![Ok(1),Ok(0),Ok(3)];
+			assert_eq!( v.clone().into_iter().ok_or(Ok(0)).collect::<Vec<_>>(), expected );
+			let expected: Vec<Result<i32,u8>> = vec![Ok(1),Err(9),Ok(3)];
+			assert_eq!( v.clone().into_iter().ok_or(Err(9_u8)).collect::<Vec<_>>(), expected );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().ok_or(Err(9_u8)).collect::<Vec<_>>(), expected );
+		}
+
+		#[cfg(test)]
+		#[test]
+		fn test_map_ok_into() {
+			let v: Vec<Result<u8,&str>> = vec![Ok(1),Err("e"),Ok(255)];
+			let expected: Vec<Result<u32,&str>> = vec![Ok(1),Err("e"),Ok(255)];
+			assert_eq!( v.clone().into_iter().map_ok_into::<u32>().collect::<Vec<_>>(), expected );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().map_ok_into::<u32>().collect::<Vec<_>>(), expected );
+		}
 	}
 
 	pub mod for_option {
@@ -892,8 +1073,82 @@ mod iter_impl {
 					where_parallel: { F: Fn() -> Option<T> + Send + Sync }
 					call: { self,input -> input.or_else(|| self.0() ) }
 				}
+				{
+					name_fn: some_or
+					name_iter_serial: SomeOr
+					name_iter_parallel: ParallelSomeOr
+					name_map_fn: SomeOrFn
+					desc: "論理和をとる。入力の `Option<T>` 型が `Some` の場合はそのまま出力され、 `None` の場合はイテレータに与えられた `Option<T>` 型の値を複製して出力する。"
+					params: [ default:O ]
+					type_params: [ O ]
+					output_type: { Option<T> }
+					where_serial: { O: Clone + Into<Option<T>> }
+					where_parallel: { O: Clone + Into<Option<T>> + Send + Sync }
+					call: { self,input -> input.or_else(|| self.0.clone().into() ) }
+				}
+				{
+					name_fn: inspect_some
+					name_iter_serial: InspectSome
+					name_iter_parallel: ParallelInspectSome
+					name_map_fn: InspectSomeFn
+					desc: "`Option<T>` 型が `Some` の場合に内包データの参照をクロージャに渡す。入力はそのまま出力される。"
+					params: [ f:F ]
+					type_params: [ F ]
+					output_type: { Option<T> }
+					where_serial: { F: FnMut(&T) }
+					where_parallel: { F: Fn(&T) + Send + Sync }
+					call: { self,input -> { if let Some(i) = &input { self.0(i); } input } }
+				}
+			]
+		}
+
+		/// `Option<T>` のイテレータを集約する終端のメソッドを提供するトレイト
+		pub trait TryCollectOption<T>: Iterator<Item=Option<T>> + Sized {
+			/// `Some` の値を `C` に集約して `Some(C)` を返す。 `None` が現れた時点で走査を打ち切り、 `None` を返す。
+			fn try_collect_option<C: FromIterator<T>>(self) -> Option<C> {
+				self.collect()
+			}
+		}
+		impl<I,T> TryCollectOption<T> for I where I: Iterator<Item=Option<T>> {}
+
+		#[cfg(test)]
+		#[test]
+		fn test_some_or() {
+			let v = vec![Some(1),None,Some(3)];
+			assert_eq!( v.clone().into_iter().some_or(Some(0)).collect::<Vec<_>>(), vec![Some(1),Some(0),Some(3)] );
+			assert_eq!( v.clone().into_iter().some_or(None).collect::<Vec<_>>(), v );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().some_or(Some(0)).collect::<Vec<_>>(), vec![Some(1),Some(0),Some(3)] );
+		}
+	}
+
+	pub mod for_result_option {
+		use super::*;
+
+		make! {
+			item_type: { T,E: Result<Option<T>,E> }
+			items: [
+				{
+					name_fn: transpose_result_option
+					name_iter_serial: TransposeResultOption
+					name_iter_parallel: ParallelTransposeResultOption
+					name_map_fn: TransposeResultOptionFn
+					desc: "`Result<Option<T>,E>` 型を `Option<Result<T,E>>` 型に入れ替える。 `Ok(Some(v))` は `Some(Ok(v))` に、 `Ok(None)` は `None` に、 `Err(e)` は `Some(Err(e))` になる。"
+					output_type: { Option<Result<T,E>> }
+					call: { self,input -> input.transpose() }
+				}
 			]
 		}
+
+		#[cfg(test)]
+		#[test]
+		fn test_transpose_result_option() {
+			let v: Vec<Result<Option<i32>,&str>> = vec![Ok(Some(1)),Ok(None),Err("e")];
+			let expected = vec![Some(Ok(1)),None,Some(Err("e"))];
+			assert_eq!( v.clone().into_iter().transpose_result_option().collect::<Vec<_>>(), expected );
+			#[cfg(feature="parallel")]
+			assert_eq!( v.into_par_iter().transpose_result_option().collect::<Vec<_>>(), expected );
+		}
 	}
 
 	pub mod for_impl_into {
@@ -922,6 +1177,243 @@ mod iter_impl {
 }
 pub use iter_impl::*;
 
+
+
+/// `Result<J,E>` の `Ok` の部分のイテレータを平坦化する `flatten_ok` を提供するモジュール。
+/// * 1つの入力から複数の出力が生じるので、 `ExtendedMapFn` による 1:1 の写像ではなく、内側のイテレータを保持する独自のイテレータとして実装している。
+pub mod flatten_ok {
+	use super::*;
+	#[cfg(feature="parallel")]
+	use std::iter::Once;
+
+	/// `flatten_ok()` にて生成されるイテレータ。 `Ok(inner)` は `inner` の各要素 `t` を `Ok(t)` として出力し、 `Err(e)` は `Err(e)` を1つだけ出力する。
+	pub struct FlattenOk<I,II> {
+		iter: I,
+		/// 現在展開中の内側のイテレータ
+		inner: Option<II>
+	}
+
+	impl<I,J,E> Iterator for FlattenOk<I,J::IntoIter>
+	where I: Iterator<Item=Result<J,E>>, J: IntoIterator
+	{
+		type Item = Result<J::Item,E>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			loop {
+				if let Some(inner) = &mut self.inner {
+					if let Some(v) = inner.next() { return Some(Ok(v)); }
+					self.inner = None;
+				}
+				match self.iter.next()? {
+					Ok(j) => { self.inner = Some(j.into_iter()); },
+					Err(e) => { return Some(Err(e)); }
+				}
+			}
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let (lower,upper) = self.inner.as_ref().map_or((0,Some(0)),|i| i.size_hint() );
+			match self.iter.size_hint() {
+				(_,Some(0)) => (lower,upper),
+				_ => (lower,None)
+			}
+		}
+	}
+
+	impl<I,J,E> FusedIterator for FlattenOk<I,J::IntoIter>
+	where I: FusedIterator<Item=Result<J,E>>, J: IntoIterator {}
+
+	/// イテレータを拡張して、 `flatten_ok` を提供するトレイト
+	pub trait IntoFlattenOk<J: IntoIterator,E>: Sized {
+		/// `Result<J,E>` のイテレータのうち、 `Ok` の部分のイテレータ `J` を平坦化して `Result<T,E>` のイテレータにする。 `Err` はそのまま1つの要素として出力される。
+		fn flatten_ok(self) -> FlattenOk<Self,J::IntoIter>;
+	}
+
+	impl<I,J,E> IntoFlattenOk<J,E> for I
+	where I: Iterator<Item=Result<J,E>>, J: IntoIterator
+	{
+		fn flatten_ok(self) -> FlattenOk<Self,J::IntoIter> {
+			FlattenOk { iter: self, inner: None }
+		}
+	}
+
+	#[cfg(feature="parallel")]
+	/// 並列イテレータの各要素を展開する直列の `FlattenOk`
+	type FlattenOkOnce<J,E> = FlattenOk<Once<Result<J,E>>,<J as IntoIterator>::IntoIter>;
+
+	#[cfg(feature="parallel")]
+	/// `flatten_ok()` にて生成される並列イテレータ。各要素を直列の `FlattenOk` で展開する `flat_map_iter` として実装している。
+	pub type ParallelFlattenOk<I,J,E> = rayon::iter::FlatMapIter<I,fn(Result<J,E>)->FlattenOkOnce<J,E>>;
+
+	#[cfg(feature="parallel")]
+	/// 並列イテレータを拡張して、 `flatten_ok` を提供するトレイト
+	pub trait IntoParallelFlattenOk<J: IntoIterator,E>: Sized {
+		/// `Result<J,E>` の並列イテレータのうち、 `Ok` の部分のイテレータ `J` を平坦化して `Result<T,E>` の並列イテレータにする。 `Err` はそのまま1つの要素として出力される。
+		fn flatten_ok(self) -> ParallelFlattenOk<Self,J,E>;
+	}
+
+	#[cfg(feature="parallel")]
+	impl<I,J,E> IntoParallelFlattenOk<J,E> for I
+	where I: ParallelIterator<Item=Result<J,E>>, J: IntoIterator, J::Item: Send, E: Send
+	{
+		fn flatten_ok(self) -> ParallelFlattenOk<Self,J,E> {
+			let f: fn(Result<J,E>)->FlattenOkOnce<J,E> = |r| std::iter::once(r).flatten_ok();
+			self.flat_map_iter(f)
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_flatten_ok() {
+		let v: Vec<Result<Vec<i32>,&str>> = vec![Ok(vec![1,2]),Err("a"),Ok(vec![]),Ok(vec![3]),Err("b")];
+		let expected = vec![Ok(1),Ok(2),Err("a"),Ok(3),Err("b")];
+		assert_eq!( v.clone().into_iter().flatten_ok().collect::<Vec<_>>(), expected );
+		#[cfg(feature="parallel")]
+		assert_eq!( v.into_par_iter().flatten_ok().collect::<Vec<_>>(), expected );
+	}
+
+}
+
+/// 要素と共にその位置のインデクスをクロージャに渡して写像する `map_with_index` を提供するモジュール。
+/// * インデクスは `enumerate` により得て、 `(usize,T)` を受け取る写像関数 `MapWithIndexFn` で写像する。
+/// * 並列の場合は共有のカウンタを持てないので、インデクス付きの並列イテレータ (`IndexedParallelIterator`) の `enumerate` により各要素の全体での位置を得る。
+pub mod map_with_index {
+	use super::*;
+	use for_serial_iter::{
+		ExtendedMap as Map,
+		ExtendedMapFn as MapFn
+	};
+	#[cfg(feature="parallel")]
+	use for_parallel_iter::{
+		ExtendedMap as ParallelMap,
+		ExtendedMapFn as ParallelMapFn
+	};
+
+	/// `map_with_index()` にて生成されるイテレータを構成する `ExtendedMap` 向けの関数
+	pub struct MapWithIndexFn<F>(F);
+
+	impl<T,U,F> MapFn<(usize,T)> for MapWithIndexFn<F>
+	where F: FnMut(usize,T) -> U
+	{
+		type Output = U;
+		fn call_mut(&mut self,(i,input):(usize,T)) -> U { self.0(i,input) }
+	}
+
+	#[cfg(feature="parallel")]
+	impl<T,U,F> ParallelMapFn<(usize,T)> for MapWithIndexFn<F>
+	where F: Fn(usize,T) -> U + Send + Sync
+	{
+		fn call(&self,(i,input):(usize,T)) -> U { self.0(i,input) }
+	}
+
+	/// `map_with_index()` にて生成されるイテレータ
+	pub type MapWithIndex<I,F> = Map<std::iter::Enumerate<I>,MapWithIndexFn<F>>;
+
+	#[cfg(feature="parallel")]
+	/// `map_with_index()` にて生成される並列イテレータ
+	pub type ParallelMapWithIndex<I,F> = ParallelMap<rayon::iter::Enumerate<I>,MapWithIndexFn<F>>;
+
+	/// イテレータを拡張して、 `map_with_index` を提供するトレイト
+	pub trait IntoMapWithIndex<T>: Iterator<Item=T> + Sized {
+		/// 各要素 `T` を、その位置のインデクスと共にクロージャ `F(usize,T)->U` で写像する。 `.enumerate().map(|(i,x)| f(i,x) )` と同等。
+		fn map_with_index<U,F>(self,f:F) -> MapWithIndex<Self,F>
+		where F: FnMut(usize,T) -> U
+		{ Map { iter: self.enumerate(), map_fn: MapWithIndexFn(f) } }
+	}
+	impl<I,T> IntoMapWithIndex<T> for I where I: Iterator<Item=T> {}
+
+	#[cfg(feature="parallel")]
+	/// 並列イテレータを拡張して、 `map_with_index` を提供するトレイト
+	pub trait IntoParallelMapWithIndex<T>: IndexedParallelIterator<Item=T> {
+		/// 各要素 `T` を、その位置のインデクスと共にクロージャ `F(usize,T)->U` で写像する。
+		/// * インデクスは各スレッドで独立に数えるのではなく、元の並列イテレータ全体での位置となる。そのため、元の並列イテレータは `IndexedParallelIterator` である必要がある。
+		fn map_with_index<U,F>(self,f:F) -> ParallelMapWithIndex<Self,F>
+		where F: Fn(usize,T) -> U + Send + Sync, U: Send
+		{ ParallelMap { parent_iterator: self.enumerate(), map_fn: MapWithIndexFn(f) } }
+	}
+	#[cfg(feature="parallel")]
+	impl<I,T> IntoParallelMapWithIndex<T> for I where I: IndexedParallelIterator<Item=T> {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_map_with_index() {
+		let v = vec!['a','b','c','d'];
+		let expected = vec![(0,'a'),(1,'b'),(2,'c'),(3,'d')];
+		assert_eq!( v.clone().into_iter().map_with_index(|i,c| (i,c) ).collect::<Vec<_>>(), expected );
+		assert_eq!( v.clone().into_iter().map_with_index(|i,c| (i,c) ).nth(2), Some((2,'c')) );
+		assert_eq!( v.clone().into_iter().map_with_index(|i,c| (i,c) ).next_back(), Some((3,'d')) );
+		#[cfg(feature="parallel")]
+		{
+			assert_eq!( v.into_par_iter().map_with_index(|i,c| (i,c) ).collect::<Vec<_>>(), expected );
+			// 十分に分割される長さでも、インデクスは全体での位置になる
+			assert!( (0..10000).into_par_iter().map_with_index(|i,x| i==x ).all(|b| b ) );
+		}
+	}
+
+}
+
+/// `Result<T,E>` の `Ok` の値に状態を持ち回りながら写像する `scan_ok` を提供するモジュール。
+/// * 状態を保持するので、状態を持たない `ExtendedMapFn` による写像ではなく、状態を保持する独自のイテレータとして実装している。
+/// * 各要素の結果が前の要素に依存するので、直列のイテレータのみに対応している。
+pub mod scan_ok {
+	use super::*;
+
+	/// `scan_ok()` にて生成されるイテレータ。 `Ok(t)` は状態と共に写像して `Ok(u)` を出力し、 `Err(e)` は状態を変えずにそのまま出力する。
+	pub struct ScanOk<I,S,F> {
+		iter: I,
+		/// 各要素の写像で持ち回る状態
+		state: S,
+		scan_fn: F
+	}
+
+	impl<I,T,E,S,U,F> Iterator for ScanOk<I,S,F>
+	where I: Iterator<Item=Result<T,E>>, F: FnMut(&mut S,T) -> U
+	{
+		type Item = Result<U,E>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let Self { iter, state, scan_fn } = self;
+			iter.next()
+			.map(|r| r.map(|t| scan_fn(state,t) ) )
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.iter.size_hint()
+		}
+	}
+
+	impl<I,T,E,S,U,F> ExactSizeIterator for ScanOk<I,S,F>
+	where I: ExactSizeIterator<Item=Result<T,E>>, F: FnMut(&mut S,T) -> U {}
+
+	impl<I,T,E,S,U,F> FusedIterator for ScanOk<I,S,F>
+	where I: FusedIterator<Item=Result<T,E>>, F: FnMut(&mut S,T) -> U {}
+
+	/// イテレータを拡張して、 `scan_ok` を提供するトレイト
+	pub trait IntoScanOk<T,E>: Iterator<Item=Result<T,E>> + Sized {
+		/// `Result<T,E>` のイテレータの `Ok` の値 `t` を、状態 `S` への可変参照と共にクロージャ `F(&mut S,T)->U` で写像して `Result<U,E>` のイテレータにする。
+		/// * 状態の初期値は `init` で与える。 `Err` の要素はクロージャに渡されず、状態を変えずにそのまま出力される。
+		/// * 直列のイテレータのみに対応している。
+		fn scan_ok<S,U,F>(self,init:S,f:F) -> ScanOk<Self,S,F>
+		where F: FnMut(&mut S,T) -> U
+		{ ScanOk { iter: self, state: init, scan_fn: f } }
+	}
+	impl<I,T,E> IntoScanOk<T,E> for I where I: Iterator<Item=Result<T,E>> {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_scan_ok() {
+		let v: Vec<Result<i32,&str>> = vec![Ok(1),Ok(2),Err("a"),Ok(3),Err("b")];
+		let running_total = |s:&mut i32,x:i32| { *s += x; *s };
+		// `Err` は合計に寄与しないが、そのまま出力される
+		let expected = vec![Ok(1),Ok(3),Err("a"),Ok(6),Err("b")];
+		assert_eq!( v.clone().into_iter().scan_ok(0,running_total).collect::<Vec<_>>(), expected );
+		assert_eq!( v.into_iter().scan_ok(0,running_total).size_hint(), (5,Some(5)) );
+	}
+
+}
+
+
+
 /// このモジュールからクレートの `prelude` でアクセスできるようにするアイテムをまとめたもの
 pub(crate) mod for_prelude {
 	pub use super::{
@@ -930,8 +1422,15 @@ pub(crate) mod for_prelude {
 			ExtendedMapFn as ExtendedMapFnForIterator
 		},
 		for_result::IntoMap as MapExtensionForResultIterator,
+		for_result::TryCollect as TryCollectForResultIterator,
+		for_result::PartitionResults as PartitionResultsForIterator,
+		for_option::TryCollectOption as TryCollectForOptionIterator,
 		for_option::IntoMap as MapExtensionForOptionIterator,
-		for_impl_into::IntoMap as MapExtensionForImplIntoIterator
+		for_result_option::IntoMap as MapExtensionForResultOptionIterator,
+		for_impl_into::IntoMap as MapExtensionForImplIntoIterator,
+		flatten_ok::IntoFlattenOk as FlattenOkExtensionForIterator,
+		map_with_index::IntoMapWithIndex as MapWithIndexExtensionForIterator,
+		scan_ok::IntoScanOk as ScanOkExtensionForIterator
 	};
 	#[cfg(feature="parallel")]
 	pub use super::{
@@ -941,6 +1440,10 @@ pub(crate) mod for_prelude {
 		},
 		for_result::IntoParallelMap as MapExtensionForResultParallelIterator,
 		for_option::IntoParallelMap as MapExtensionForOptionParallelIterator,
-		for_impl_into::IntoParallelMap as MapExtensionForImplIntoParallelIterator
+		for_result::ParallelPartitionResults as PartitionResultsForParallelIterator,
+		for_result_option::IntoParallelMap as MapExtensionForResultOptionParallelIterator,
+		for_impl_into::IntoParallelMap as MapExtensionForImplIntoParallelIterator,
+		flatten_ok::IntoParallelFlattenOk as FlattenOkExtensionForParallelIterator,
+		map_with_index::IntoParallelMapWithIndex as MapWithIndexExtensionForParallelIterator
 	};
 }
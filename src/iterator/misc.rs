@@ -17,7 +17,7 @@ mod cycle_n {
 
 	impl<I: ICS> IteratorCycleNExtension<I> for I {
 		fn cycle_n(self,repeat:usize) -> CycleN<I> {
-			CycleN { iterator: self.clone(), original: self, whole_count: repeat, current_count: repeat }
+			CycleN { iterator: self.clone(), original: self, back_iterator: None, current_count: repeat }
 		}
 	}
 
@@ -25,8 +25,11 @@ mod cycle_n {
 	#[derive(Clone)]
 	pub struct CycleN<I: ICS> {
 		original: I,
+		/// 前から取り出している周回のイテレータ
 		iterator: I,
-		whole_count: usize,
+		/// 後ろから取り出している周回のイテレータ。残りが1周の場合は前後で `iterator` を共有するので `None` となる
+		back_iterator: Option<I>,
+		/// 取り出しきっていない周回の数 (前後で取り出し途中の周回を含む)
 		current_count: usize
 	}
 
@@ -36,29 +39,73 @@ mod cycle_n {
 
 		#[inline]
 		fn next(&mut self) -> Option<Self::Item> {
-			match (self.iterator.next(),self.current_count) {
-				(_,0) => None,
-				(None,1) => None,
-				(None,_) => {
-					self.current_count -= 1;
-					self.iterator = self.original.clone();
-					self.iterator.next()
-				},
-				(s,_) => s
+			loop {
+				if self.current_count==0 { return None; }
+				if let s @ Some(_) = self.iterator.next() { return s; }
+				self.current_count -= 1;
+				// 残りが後ろから取り出し途中の周回だけになった場合はそれを引き継ぐ
+				self.iterator = match (self.current_count,self.back_iterator.take()) {
+					(1,Some(b)) => b,
+					(_,b) => { self.back_iterator = b; self.original.clone() }
+				};
 			}
 		}
 
 		#[inline]
 		fn size_hint(&self) -> (usize, Option<usize>) {
-			match (self.original.size_hint(),self.whole_count) {
-				((0,Some(0)),_)|(_,0) => (0,Some(0)),
-				((l,u),n) => (
-					l.checked_mul(n).unwrap_or(usize::MAX),
-					u.and_then(|u| u.checked_mul(n) )
-				)
+			if self.current_count==0 { return (0,Some(0)); }
+			let partial = 1 + self.back_iterator.is_some() as usize;
+			let full = self.current_count - partial;
+			let (fl,fu) = self.iterator.size_hint();
+			let (bl,bu) = self.back_iterator.as_ref().map_or((0,Some(0)),|b| b.size_hint() );
+			let (ol,ou) = self.original.size_hint();
+			(
+				ol.saturating_mul(full).saturating_add(fl).saturating_add(bl),
+				ou.and_then(|u| u.checked_mul(full) )
+				.and_then(|u| u.checked_add(fu?) )
+				.and_then(|u| u.checked_add(bu?) )
+			)
+		}
+
+	}
+
+	impl<I: ICS + DoubleEndedIterator> DoubleEndedIterator for CycleN<I> {
+		#[inline]
+		fn next_back(&mut self) -> Option<Self::Item> {
+			loop {
+				match self.current_count {
+					0 => { return None; },
+					1 => { return self.iterator.next_back(); },
+					_ => {}
+				}
+				let back = self.back_iterator.get_or_insert_with(|| self.original.clone() );
+				if let s @ Some(_) = back.next_back() { return s; }
+				self.current_count -= 1;
+				self.back_iterator = None;
 			}
 		}
+	}
+
+	impl<I: ICS + ExactSizeIterator> ExactSizeIterator for CycleN<I> {}
 
+	#[cfg(test)]
+	#[test]
+	fn test_cycle_n() {
+		assert_eq!( (0..2).cycle_n(3).collect::<Vec<_>>(), vec![0,1,0,1,0,1] );
+		assert_eq!( (0..2).cycle_n(3).len(), 6 );
+		assert_eq!( (0..2).cycle_n(3).rev().collect::<Vec<_>>(), vec![1,0,1,0,1,0] );
+		assert_eq!( (0..0).cycle_n(3).len(), 0 );
+		assert_eq!( (0..2).cycle_n(0).next(), None );
+
+		// 前後から交互に取り出しても、長さと要素が整合する
+		let mut c = (0..3).cycle_n(2);
+		assert_eq!( (c.next(),c.next_back(),c.len()), (Some(0),Some(2),4) );
+		assert_eq!( (c.next(),c.next(),c.next(),c.len()), (Some(1),Some(2),Some(0),1) );
+		assert_eq!( (c.next_back(),c.next(),c.next_back()), (Some(1),None,None) );
+
+		let mut c = (0..2).cycle_n(3);
+		assert_eq!( (c.next_back(),c.next_back(),c.next_back(),c.len()), (Some(1),Some(0),Some(1),3) );
+		assert_eq!( c.collect::<Vec<_>>(), vec![0,1,0] );
 	}
 
 }
@@ -76,21 +123,32 @@ mod min_max {
 
 	compose_struct! {
 		pub type OptMinMax<T> = Option<(T,T)>;
+		pub type OptMinMaxPosition<T> = Option<((usize,T),(usize,T))>;
 		pub trait Iter<T> = Iterator<Item=T> + Sized;
-		pub trait Item = Clone + Ord;
+		pub trait Item = Clone;
 		pub trait OrdFn<T> = FnMut(&T,&T) -> Ordering;
+		pub trait KeyFn<T,K> = FnMut(&T) -> K;
 	}
 
 	pub trait IteratorMinMaxExtension<I,T> {
 		/// イテレータに対して最大値と最小値の両方を同時に計算する
-		fn min_max(self) -> OptMinMax<T>;
+		fn min_max(self) -> OptMinMax<T> where T: Ord;
 		/// イテレータに対して指定した計算方法を用いて最大値と最小値の両方を同時に計算する
 		fn min_max_by(self,compare:impl OrdFn<T>) -> OptMinMax<T>;
+		/// イテレータに対して各要素から得たキーを用いて最大値と最小値の両方を同時に計算する
+		/// * キーは各要素につき1回だけ計算します。
+		/// * キーが等しい要素が複数ある場合、 `min_by_key` / `max_by_key` と同様に最小値は最初の要素を、最大値は最後の要素を返します。
+		fn min_max_by_key<K:Ord>(self,key:impl KeyFn<T,K>) -> OptMinMax<T>;
+		/// イテレータに対して最小値と最大値を、それぞれの位置 (何番目の要素か) と共に同時に計算する
+		/// * 等しい値が複数ある場合、最小値は最初の位置を、最大値は最後の位置を返します。
+		fn min_max_position(self) -> OptMinMaxPosition<T> where T: Ord;
+		/// イテレータに対して指定した計算方法を用いて最小値と最大値を、それぞれの位置と共に同時に計算する
+		fn min_max_position_by(self,compare:impl OrdFn<T>) -> OptMinMaxPosition<T>;
 	}
 
 	impl<I:Iter<T>,T:Item> IteratorMinMaxExtension<I,T> for I {
 
-		fn min_max(self) -> OptMinMax<T> {
+		fn min_max(self) -> OptMinMax<T> where T: Ord {
 			self.min_max_by(Ord::cmp)
 		}
 
@@ -108,7 +166,738 @@ mod min_max {
 			) )
 		}
 
+		fn min_max_by_key<K:Ord>(mut self,mut key:impl KeyFn<T,K>)
+		-> OptMinMax<T> {
+			let first = self.next()?;
+			let (min_key,max_key) = (key(&first),key(&first));
+			let ((_,min_val),(_,max_val)) = self.fold(
+				((min_key,first.clone()),(max_key,first)),
+				move |(min_pair,max_pair),item| {
+					// 最小のキーは常に最大のキー以下なので、両方が更新されることはない
+					let k = key(&item);
+					if k<min_pair.0 { ((k,item),max_pair) }
+					else if k>=max_pair.0 { (min_pair,(k,item)) }
+					else { (min_pair,max_pair) }
+				}
+			);
+			Some((min_val,max_val))
+		}
+
+		fn min_max_position(self) -> OptMinMaxPosition<T> where T: Ord {
+			self.min_max_position_by(Ord::cmp)
+		}
+
+		fn min_max_position_by(self,mut compare:impl OrdFn<T>)
+		-> OptMinMaxPosition<T> {
+			self.enumerate()
+			.min_max_by(|(_,a),(_,b)| compare(a,b) )
+		}
+
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_min_max_by_key() {
+		#[derive(Clone,Debug,PartialEq)]
+		struct Person { name: &'static str, age: u32 }
+		let people = [
+			Person { name: "a", age: 30 },
+			Person { name: "b", age: 20 },
+			Person { name: "c", age: 40 },
+			Person { name: "d", age: 20 },
+			Person { name: "e", age: 40 },
+		];
+		let (youngest,oldest) = people.iter().cloned().min_max_by_key(|p| p.age ).unwrap();
+		assert_eq!( youngest.name, "b" );
+		assert_eq!( oldest.name, "e" );
+		assert_eq!( people.iter().min_by_key(|p| p.age ), Some(&youngest) );
+		assert_eq!( people.iter().max_by_key(|p| p.age ), Some(&oldest) );
+		assert_eq!( people[..1].iter().min_max_by_key(|p| p.age ).map(|(a,b)| (a.name,b.name) ), Some(("a","a")) );
+		assert_eq!( std::iter::empty::<u8>().min_max_by_key(|&v| v ), None );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_min_max_position() {
+		let v = [3,1,4,1,5,9,2,6,9,3];
+		assert_eq!( v.iter().copied().min_max_position(), Some(((1,1),(8,9))) );
+		assert_eq!( v.iter().copied().min_max_position_by(|a,b| b.cmp(a) ), Some(((5,9),(3,1))) );
+		assert_eq!( [7].iter().min_max_position(), Some(((0,&7),(0,&7))) );
+		assert_eq!( std::iter::empty::<u8>().min_max_position(), None );
 	}
 
 }
 pub use min_max::IteratorMinMaxExtension;
+
+
+
+/// イテレータの最初と最後の要素を同時に取り出すメソッドを追加するモジュール
+mod first_and_last {
+	use super::*;
+
+	compose_struct! {
+		pub type OptFirstLast<T> = Option<(T,T)>;
+	}
+
+	pub trait IteratorFirstAndLastExtension<T>: Iterator<Item=T> + Sized {
+		/// イテレータの最初と最後の要素を1回の走査で取り出します。要素が1つの場合は両方とも同じ値になり、空の場合は `None` を返します。
+		fn first_and_last(mut self) -> OptFirstLast<T> where T: Clone {
+			let first = self.next()?;
+			let last = self.last().unwrap_or_else(|| first.clone() );
+			Some((first,last))
+		}
+		/// イテレータの最初と最後の要素を両側から直接取り出します。途中の要素を走査しません。
+		fn first_and_last_double_ended(mut self) -> OptFirstLast<T>
+		where T: Clone, Self: DoubleEndedIterator {
+			let first = self.next()?;
+			let last = self.next_back().unwrap_or_else(|| first.clone() );
+			Some((first,last))
+		}
+	}
+
+	impl<I:Iterator<Item=T>,T> IteratorFirstAndLastExtension<T> for I {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_first_and_last() {
+		assert_eq!( (0..0).first_and_last(), None );
+		assert_eq!( (3..4).first_and_last(), Some((3,3)) );
+		assert_eq!( (3..9).filter(|i| i%2==1 ).first_and_last(), Some((3,7)) );
+		assert_eq!( (0..0).first_and_last_double_ended(), None );
+		assert_eq!( (3..4).first_and_last_double_ended(), Some((3,3)) );
+		assert_eq!( (3..9).first_and_last_double_ended(), Some((3,8)) );
+	}
+
+}
+pub use first_and_last::IteratorFirstAndLastExtension;
+
+
+
+/// イテレータを指定した位置で前後2つのイテレータに分割するメソッドを追加するモジュール
+mod split_at {
+	use super::*;
+	use std::{
+		rc::Rc,
+		cell::RefCell,
+		collections::VecDeque
+	};
+
+	/// 前後のイテレータで共有する元のイテレータの状態
+	struct Shared<I: Iterator> {
+		source: I,
+		/// 前半のうち、まだ元のイテレータから取り出されていない要素数
+		head_remaining: usize,
+		/// 後半のイテレータが先に進められた際に、前半の要素を一時的に保持するバッファ
+		head_buffer: VecDeque<I::Item>
+	}
+
+	/// `split_at_iter()` にて生成される前半のイテレータ
+	pub struct SplitHead<I: Iterator> {
+		shared: Rc<RefCell<Shared<I>>>
+	}
+
+	/// `split_at_iter()` にて生成される後半のイテレータ
+	pub struct SplitTail<I: Iterator> {
+		shared: Rc<RefCell<Shared<I>>>
+	}
+
+	pub trait IteratorSplitAtExtension: Iterator + Sized {
+		/// イテレータを最初の `n` 個の要素を返すイテレータと、残りの要素を返すイテレータに分割します。スライスの `split_at` に相当します。
+		/// * 2つのイテレータは元のイテレータを共有し、必要になった時点で要素を取り出します。
+		/// * 前半を読み切る前に後半を進めた場合、前半の未読の要素は全てバッファに移されてから後半の要素が取り出されます。そのため、最大で `n` 個の要素がメモリ上に保持されます。
+		fn split_at_iter(self,n:usize) -> (SplitHead<Self>,SplitTail<Self>) {
+			let shared = Rc::new(RefCell::new(Shared {
+				source: self,
+				head_remaining: n,
+				head_buffer: VecDeque::new()
+			}));
+			( SplitHead { shared: shared.clone() }, SplitTail { shared } )
+		}
+	}
+
+	impl<I: Iterator> IteratorSplitAtExtension for I {}
+
+	impl<I: Iterator> Iterator for SplitHead<I> {
+		type Item = I::Item;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let mut s = self.shared.borrow_mut();
+			if let Some(v) = s.head_buffer.pop_front() { return Some(v); }
+			if s.head_remaining==0 { return None; }
+			match s.source.next() {
+				v @ Some(_) => { s.head_remaining -= 1; v },
+				None => { s.head_remaining = 0; None }
+			}
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let s = self.shared.borrow();
+			let (l,u) = s.source.size_hint();
+			let b = s.head_buffer.len();
+			let r = s.head_remaining;
+			( b+l.min(r), Some(b+u.map_or(r,|u| u.min(r) )) )
+		}
+	}
+
+	impl<I: Iterator> Iterator for SplitTail<I> {
+		type Item = I::Item;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let mut s = self.shared.borrow_mut();
+			// 前半の未読の要素をバッファに移してから後半の要素を取り出す
+			while s.head_remaining>0 {
+				s.head_remaining -= 1;
+				match s.source.next() {
+					Some(v) => { s.head_buffer.push_back(v); },
+					None => { s.head_remaining = 0; return None; }
+				}
+			}
+			s.source.next()
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let s = self.shared.borrow();
+			let (l,u) = s.source.size_hint();
+			let r = s.head_remaining;
+			( l.saturating_sub(r), u.map(|u| u.saturating_sub(r) ) )
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_split_at_iter() {
+		let (head,tail) = (0..6).split_at_iter(3);
+		assert_eq!( (head.size_hint(),tail.size_hint()), ((3,Some(3)),(3,Some(3))) );
+		assert_eq!( head.collect::<Vec<_>>(), vec![0,1,2] );
+		assert_eq!( tail.collect::<Vec<_>>(), vec![3,4,5] );
+
+		// 後半を先に読んでも、前半はバッファから取り出される
+		let (head,mut tail) = (0..6).split_at_iter(3);
+		assert_eq!( tail.next(), Some(3) );
+		assert_eq!( head.collect::<Vec<_>>(), vec![0,1,2] );
+		assert_eq!( tail.collect::<Vec<_>>(), vec![4,5] );
+
+		let (head,tail) = (0..2).split_at_iter(3);
+		assert_eq!( tail.collect::<Vec<_>>(), vec![] );
+		assert_eq!( head.collect::<Vec<_>>(), vec![0,1] );
+	}
+
+}
+pub use split_at::{IteratorSplitAtExtension,SplitHead,SplitTail};
+
+
+
+/// 二項演算による累積値を順に返すイテレータを生成するモジュール
+mod accumulate {
+	use super::*;
+
+	pub trait IteratorAccumulateExtension<T>: Iterator<Item=T> + Sized {
+		/// 二項演算 `op` による累積値を順に返すイテレータを生成します。
+		/// * 最初の値は最初の要素そのもので、以降は直前の累積値 `acc` と次の要素 `x` から `op(acc,x)` を返します。
+		/// * `op` に加算を与えれば累積和、 `max` を与えれば累積最大値となります。
+		fn accumulate<F>(self,op:F) -> Accumulate<Self,F>
+		where T: Clone, F: FnMut(T,T) -> T
+		{ Accumulate { iter: self, acc: None, op } }
+	}
+
+	impl<I:Iterator<Item=T>,T> IteratorAccumulateExtension<T> for I {}
+
+	/// 二項演算による累積値を順に返すイテレータ
+	#[derive(Clone)]
+	pub struct Accumulate<I: Iterator,F> {
+		iter: I,
+		acc: Option<I::Item>,
+		op: F
+	}
+
+	impl<I,T,F> Iterator for Accumulate<I,F>
+	where I: Iterator<Item=T>, T: Clone, F: FnMut(T,T) -> T
+	{
+		type Item = T;
+
+		fn next(&mut self) -> Option<T> {
+			let x = self.iter.next()?;
+			let acc = match self.acc.take() {
+				Some(acc) => (self.op)(acc,x),
+				None => x
+			};
+			self.acc = Some(acc.clone());
+			Some(acc)
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.iter.size_hint()
+		}
+	}
+
+	impl<I,T,F> ExactSizeIterator for Accumulate<I,F>
+	where I: ExactSizeIterator<Item=T>, T: Clone, F: FnMut(T,T) -> T {}
+
+	impl<I,T,F> FusedIterator for Accumulate<I,F>
+	where I: FusedIterator<Item=T>, T: Clone, F: FnMut(T,T) -> T {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_accumulate() {
+		assert_eq!( (1..=5).accumulate(|a,b| a+b ).collect::<Vec<_>>(), vec![1,3,6,10,15] );
+		assert_eq!(
+			[1.0,3.0,2.0,5.0,4.0].into_iter().accumulate(f64::max).collect::<Vec<_>>(),
+			vec![1.0,3.0,3.0,5.0,5.0]
+		);
+		fn gcd(a:u32,b:u32) -> u32 { if b==0 { a } else { gcd(b,a%b) } }
+		assert_eq!( [48,36,30,7].into_iter().accumulate(gcd).collect::<Vec<_>>(), vec![48,12,6,1] );
+		assert_eq!( (0..0).accumulate(|a,b| a+b ).next(), None );
+	}
+
+}
+pub use accumulate::{IteratorAccumulateExtension,Accumulate};
+
+
+
+/// 先頭の要素を読み飛ばしてから一定間隔で要素を取り出すイテレータを生成するモジュール
+mod step_with_offset {
+	use super::*;
+
+	pub trait IteratorStepExtension: Iterator + Sized {
+		/// 最初の `offset` 個の要素を読み飛ばし、その後 `step` 個ごとに要素を取り出すイテレータを生成します。
+		/// * `.skip(offset).step_by(step)` と同じ要素を返しますが、1つのイテレータとして `size_hint` を正確に計算します。
+		/// * `step` が `0` の場合はパニックします。
+		fn step_with_offset(self,step:usize,offset:usize) -> StepWithOffset<Self> {
+			assert!(step!=0,"step に 0 は指定できません");
+			StepWithOffset { iter: self, step, offset: Some(offset) }
+		}
+	}
+
+	impl<I: Iterator> IteratorStepExtension for I {}
+
+	/// 先頭の要素を読み飛ばしてから一定間隔で要素を取り出すイテレータ
+	#[derive(Clone)]
+	pub struct StepWithOffset<I> {
+		iter: I,
+		step: usize,
+		/// 最初の要素を取り出す前に読み飛ばす要素数。最初の要素を取り出した後は `None` となる
+		offset: Option<usize>
+	}
+
+	impl<I: Iterator> StepWithOffset<I> {
+		/// 元のイテレータの残り要素数 `n` から、このイテレータの残り要素数を計算する
+		fn count_from(&self,n:usize) -> usize {
+			match self.offset {
+				Some(o) => match n.saturating_sub(o) {
+					0 => 0,
+					r => (r-1)/self.step + 1
+				},
+				None => n/self.step
+			}
+		}
+	}
+
+	impl<I: Iterator> Iterator for StepWithOffset<I> {
+		type Item = I::Item;
+
+		#[inline]
+		fn next(&mut self) -> Option<Self::Item> {
+			let skip = self.offset.take().unwrap_or(self.step-1);
+			self.iter.nth(skip)
+		}
+
+		#[inline]
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let (l,u) = self.iter.size_hint();
+			( self.count_from(l), u.map(|u| self.count_from(u) ) )
+		}
+	}
+
+	impl<I: ExactSizeIterator> ExactSizeIterator for StepWithOffset<I> {}
+
+	impl<I: FusedIterator> FusedIterator for StepWithOffset<I> {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_step_with_offset() {
+		let mut s = (0..10).step_with_offset(3,1);
+		assert_eq!( s.len(), 3 );
+		assert_eq!( s.next(), Some(1) );
+		assert_eq!( s.len(), 2 );
+		assert_eq!( s.collect::<Vec<_>>(), vec![4,7] );
+		assert_eq!( (0..10).step_with_offset(3,0).collect::<Vec<_>>(), vec![0,3,6,9] );
+		assert_eq!( (0..10).step_with_offset(3,0).len(), 4 );
+		assert_eq!( (0..10).step_with_offset(1,12).len(), 0 );
+		assert_eq!( (0..10).step_with_offset(4,9).collect::<Vec<_>>(), vec![9] );
+	}
+
+}
+pub use step_with_offset::{IteratorStepExtension,StepWithOffset};
+
+
+
+/// 連続する等しい要素をまとめるイテレータを生成するモジュール
+mod group_runs {
+	use super::*;
+
+	pub trait IteratorGroupRunsExtension: Iterator + Sized {
+		/// 連続する等しい要素をまとめ、その値と連続する個数の組 `(T,usize)` を順に返すイテレータを生成します (ランレングス)。
+		fn group_runs(self) -> GroupRuns<Self> where Self::Item: PartialEq {
+			GroupRuns { iter: self, lookahead: None }
+		}
+	}
+
+	impl<I: Iterator> IteratorGroupRunsExtension for I {}
+
+	/// 連続する等しい要素をまとめるイテレータ
+	#[derive(Clone)]
+	pub struct GroupRuns<I: Iterator> {
+		iter: I,
+		/// 直前の連続の終わりを判定する際に先読みした、次の連続の最初の要素
+		lookahead: Option<I::Item>
+	}
+
+	impl<I,T> Iterator for GroupRuns<I>
+	where I: Iterator<Item=T>, T: PartialEq
+	{
+		type Item = (T,usize);
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let value = self.lookahead.take().or_else(|| self.iter.next() )?;
+			let mut count = 1;
+			for v in self.iter.by_ref() {
+				if v==value { count += 1; }
+				else {
+					self.lookahead = Some(v);
+					break;
+				}
+			}
+			Some((value,count))
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let pending = self.lookahead.is_some() as usize;
+			let (l,u) = self.iter.size_hint();
+			(
+				(pending+l).min(1),
+				u.and_then(|u| u.checked_add(pending) )
+			)
+		}
+	}
+
+	impl<I,T> FusedIterator for GroupRuns<I>
+	where I: FusedIterator<Item=T>, T: PartialEq {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_group_runs() {
+		assert_eq!( [1,1,2,3,3,3].into_iter().group_runs().collect::<Vec<_>>(), vec![(1,2),(2,1),(3,3)] );
+		assert_eq!( "aabccc".chars().group_runs().collect::<Vec<_>>(), vec![('a',2),('b',1),('c',3)] );
+		assert_eq!( [7].into_iter().group_runs().collect::<Vec<_>>(), vec![(7,1)] );
+		assert_eq!( Vec::<i32>::new().into_iter().group_runs().next(), None );
+
+		let mut g = [1,1,2,3,3,3].into_iter().group_runs();
+		assert_eq!( g.size_hint(), (1,Some(6)) );
+		g.next();
+		assert_eq!( g.size_hint(), (1,Some(4)) );
+		g.next(); g.next();
+		assert_eq!( g.size_hint(), (0,Some(0)) );
+	}
+
+}
+pub use group_runs::{IteratorGroupRunsExtension,GroupRuns};
+
+
+
+/// 重なり合う一定の長さの区間を順に返すイテレータを生成するモジュール
+mod windows {
+	use super::*;
+	use std::collections::VecDeque;
+
+	pub trait IteratorWindowsExtension: Iterator + Sized {
+		/// 長さ `n` の重なり合う区間 (スライスの `windows` に相当) を `Vec` として順に返すイテレータを生成します。
+		/// * 元のイテレータの要素数が `n` 未満の場合は何も返しません。
+		/// * `n` が `0` の場合はパニックします。
+		fn windows(self,n:usize) -> Windows<Self> where Self::Item: Clone {
+			assert!(n!=0,"区間の長さに 0 は指定できません");
+			Windows { iter: self, size: n, buffer: VecDeque::with_capacity(n), state: State::NotStarted }
+		}
+	}
+
+	impl<I: Iterator> IteratorWindowsExtension for I {}
+
+	#[derive(Clone,Copy,PartialEq,Eq)]
+	enum State { NotStarted, Running, Finished }
+
+	/// 重なり合う一定の長さの区間を順に返すイテレータ
+	#[derive(Clone)]
+	pub struct Windows<I: Iterator> {
+		iter: I,
+		size: usize,
+		/// 直前に返した区間を保持するリングバッファ
+		buffer: VecDeque<I::Item>,
+		state: State
+	}
+
+	impl<I,T> Iterator for Windows<I>
+	where I: Iterator<Item=T>, T: Clone
+	{
+		type Item = Vec<T>;
+
+		fn next(&mut self) -> Option<Vec<T>> {
+			match self.state {
+				State::Finished => { return None; },
+				State::NotStarted => {
+					self.state = State::Running;
+					self.buffer.extend(self.iter.by_ref().take(self.size));
+					if self.buffer.len()<self.size { self.state = State::Finished; }
+				},
+				State::Running => match self.iter.next() {
+					Some(v) => {
+						self.buffer.pop_front();
+						self.buffer.push_back(v);
+					},
+					None => { self.state = State::Finished; }
+				}
+			}
+			if self.state==State::Finished {
+				self.buffer.clear();
+				return None;
+			}
+			Some(self.buffer.iter().cloned().collect())
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let (l,u) = self.iter.size_hint();
+			match self.state {
+				State::Finished => (0,Some(0)),
+				State::Running => (l,u),
+				State::NotStarted => {
+					let count = |n:usize| n.saturating_sub(self.size-1);
+					(count(l),u.map(count))
+				}
+			}
+		}
+	}
+
+	impl<I,T> ExactSizeIterator for Windows<I>
+	where I: ExactSizeIterator<Item=T>, T: Clone {}
+
+	impl<I,T> FusedIterator for Windows<I>
+	where I: Iterator<Item=T>, T: Clone {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_windows() {
+		let w = (0..5).windows(3);
+		assert_eq!( w.len(), 3 );
+		assert_eq!( w.collect::<Vec<_>>(), vec![vec![0,1,2],vec![1,2,3],vec![2,3,4]] );
+		assert_eq!( (0..3).windows(3).collect::<Vec<_>>(), vec![vec![0,1,2]] );
+		assert_eq!( (0..2).windows(3).len(), 0 );
+		assert_eq!( (0..2).windows(3).next(), None );
+		assert_eq!( "abc".chars().windows(1).collect::<Vec<_>>(), vec![vec!['a'],vec!['b'],vec!['c']] );
+
+		let mut w = (0..100).windows(10);
+		assert_eq!( w.len(), 91 );
+		w.next();
+		assert_eq!( w.len(), 90 );
+		assert_eq!( w.last(), Some((90..100).collect::<Vec<_>>()) );
+	}
+
+}
+pub use windows::{IteratorWindowsExtension,Windows};
+
+
+
+/// 2つの数値のイテレータを要素ごとに演算するメソッドを追加するモジュール
+mod elementwise {
+	use super::*;
+	use std::{
+		iter::Map,
+		ops::{Add,Mul}
+	};
+	use zip::for_iters::{IntoZip,Zip};
+
+	compose_struct! {
+		pub type Elementwise<I,J,O> = Map<Zip<(I,J)>,fn((<I as Iterator>::Item,<J as Iterator>::Item))->O>;
+		pub type ElementwiseAdd<I,J> = Elementwise<I,J,<<I as Iterator>::Item as Add<<J as Iterator>::Item>>::Output>;
+		pub type ElementwiseMul<I,J> = Elementwise<I,J,<<I as Iterator>::Item as Mul<<J as Iterator>::Item>>::Output>;
+	}
+
+	pub trait IteratorElementwiseExtension: Iterator + Sized {
+		/// 2つのイテレータの要素ごとの和を返すイテレータを生成します。どちらかのイテレータが末尾に達した時点で終了します。
+		fn elementwise_add<J>(self,other:J) -> ElementwiseAdd<Self,J::IntoIter>
+		where J: IntoIterator, Self::Item: Add<J::Item> {
+			(self,other.into_iter()).zip().map(add as fn(_)->_)
+		}
+		/// 2つのイテレータの要素ごとの積を返すイテレータを生成します。どちらかのイテレータが末尾に達した時点で終了します。
+		fn elementwise_mul<J>(self,other:J) -> ElementwiseMul<Self,J::IntoIter>
+		where J: IntoIterator, Self::Item: Mul<J::Item> {
+			(self,other.into_iter()).zip().map(mul as fn(_)->_)
+		}
+	}
+
+	impl<I: Iterator> IteratorElementwiseExtension for I {}
+
+	fn add<A: Add<B>,B>((a,b):(A,B)) -> A::Output { a+b }
+	fn mul<A: Mul<B>,B>((a,b):(A,B)) -> A::Output { a*b }
+
+	#[cfg(test)]
+	#[test]
+	fn test_elementwise() {
+		assert_eq!( (0..5).elementwise_add(10..15).collect::<Vec<_>>(), vec![10,12,14,16,18] );
+		assert_eq!( (1..4).elementwise_mul(vec![2,3,4]).collect::<Vec<_>>(), vec![2,6,12] );
+		assert_eq!( [0.5,1.5].iter().elementwise_add(&[1.0,2.0,3.0]).collect::<Vec<f64>>(), vec![1.5,3.5] );
+		assert_eq!( (0..3).elementwise_add(0..0).count(), 0 );
+	}
+
+}
+pub use elementwise::{IteratorElementwiseExtension,ElementwiseAdd,ElementwiseMul};
+
+
+
+/// 失敗しうる演算によるリダクションを行うメソッドを追加するモジュール
+mod try_reduce {
+	use super::*;
+
+	pub trait IteratorTryReduceExtension: Iterator + Sized {
+		/// 失敗しうる演算 `f` を用いて `reduce` を行います。
+		/// * 演算が `Err` を返した時点で処理を中断し、その `Err` を返します。
+		/// * イテレータが空の場合は `Ok(None)` を返します。
+		fn try_reduce<E>(mut self,mut f:impl FnMut(Self::Item,Self::Item)->Result<Self::Item,E>) -> Result<Option<Self::Item>,E> {
+			let Some(first) = self.next() else { return Ok(None); };
+			self.try_fold(first,&mut f).map(Some)
+		}
+	}
+
+	impl<I: Iterator> IteratorTryReduceExtension for I {}
+
+	#[cfg(feature="parallel")]
+	pub trait ParallelIteratorTryReduceExtension: ParallelIterator {
+		/// `try_reduce` の並列版です。 rayon の `try_reduce` と区別するために名前を変えています。
+		/// * 演算が `Err` を返した場合はその `Err` を返します。複数の `Err` が生じた場合にどれが返されるかは不定です。
+		/// * イテレータが空の場合は `Ok(None)` を返します。
+		fn par_try_reduce<E>(self,f:impl Fn(Self::Item,Self::Item)->Result<Self::Item,E>+Sync+Send) -> Result<Option<Self::Item>,E>
+		where E: Send {
+			self.map(Ok).try_reduce_with(f).transpose()
+		}
+	}
+
+	#[cfg(feature="parallel")]
+	impl<I: ParallelIterator> ParallelIteratorTryReduceExtension for I {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_try_reduce() {
+		let checked_sum = |a:u8,b:u8| a.checked_add(b).ok_or(a);
+		assert_eq!( (1..=10).try_reduce(checked_sum), Ok(Some(55)) );
+		assert_eq!( (100..110).try_reduce(checked_sum), Err(201) );
+		assert_eq!( (0..0).try_reduce(checked_sum), Ok(None) );
+
+		#[cfg(feature="parallel")]
+		{
+			use rayon::iter::IntoParallelIterator;
+			let checked_sum = |a:u32,b:u32| if a+b>1000 { Err("overflow") } else { Ok(a+b) };
+			assert_eq!( (1..=40).into_par_iter().par_try_reduce(checked_sum), Ok(Some(820)) );
+			assert_eq!( (1..=50).into_par_iter().par_try_reduce(checked_sum), Err("overflow") );
+			assert_eq!( (0..0).into_par_iter().par_try_reduce(checked_sum), Ok(None) );
+		}
+	}
+
+}
+pub use try_reduce::IteratorTryReduceExtension;
+#[cfg(feature="parallel")]
+pub use try_reduce::ParallelIteratorTryReduceExtension;
+
+
+
+/// 各要素を指定した回数ずつ連続して繰り返すイテレータを生成するモジュール
+mod repeat_each {
+	use super::*;
+
+	pub trait IteratorRepeatEachExtension: Iterator + Sized {
+		/// 各要素を `n` 回ずつ連続して返すイテレータを生成します。アップサンプリングなどに使用します。
+		/// * `n` が `0` の場合は何も返しません。
+		fn repeat_each(self,n:usize) -> RepeatEach<Self> where Self::Item: Clone {
+			RepeatEach { iter: self, n, current: None, remaining: 0 }
+		}
+	}
+
+	impl<I: Iterator> IteratorRepeatEachExtension for I {}
+
+	/// 各要素を指定した回数ずつ連続して繰り返すイテレータ
+	#[derive(Clone)]
+	pub struct RepeatEach<I: Iterator> {
+		iter: I,
+		n: usize,
+		/// 現在繰り返している要素
+		current: Option<I::Item>,
+		/// 現在の要素を返す残りの回数
+		remaining: usize
+	}
+
+	impl<I,T> Iterator for RepeatEach<I>
+	where I: Iterator<Item=T>, T: Clone
+	{
+		type Item = T;
+
+		fn next(&mut self) -> Option<T> {
+			if self.remaining==0 {
+				if self.n==0 { return None; }
+				self.current = Some(self.iter.next()?);
+				self.remaining = self.n;
+			}
+			self.remaining -= 1;
+			match self.remaining {
+				0 => self.current.take(),
+				_ => self.current.clone()
+			}
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			if self.n==0 { return (0,Some(0)); }
+			let (l,u) = self.iter.size_hint();
+			(
+				l.saturating_mul(self.n).saturating_add(self.remaining),
+				u.and_then(|u| u.checked_mul(self.n)?.checked_add(self.remaining) )
+			)
+		}
+	}
+
+	impl<I,T> FusedIterator for RepeatEach<I>
+	where I: FusedIterator<Item=T>, T: Clone {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_repeat_each() {
+		assert_eq!( ['a','b'].into_iter().repeat_each(3).collect::<String>(), "aaabbb" );
+		assert_eq!( (0..3).repeat_each(1).collect::<Vec<_>>(), vec![0,1,2] );
+		assert_eq!( (0..3).repeat_each(0).count(), 0 );
+		assert_eq!( (0..0).repeat_each(2).count(), 0 );
+
+		let mut r = (0..4).repeat_each(2);
+		assert_eq!( r.size_hint(), (8,Some(8)) );
+		r.next();
+		assert_eq!( r.size_hint(), (7,Some(7)) );
+		assert_eq!( r.collect::<Vec<_>>(), vec![0,1,1,2,2,3,3] );
+		assert_eq!( (0..).repeat_each(2).size_hint(), (usize::MAX,None) );
+	}
+
+}
+pub use repeat_each::{IteratorRepeatEachExtension,RepeatEach};
+
+
+
+/// このモジュールからクレートの `prelude` でアクセスできるようにするアイテムをまとめたもの
+pub(crate) mod for_prelude {
+	pub use super::{
+		IteratorCycleNExtension,
+		IteratorMinMaxExtension,
+		IteratorFirstAndLastExtension,
+		IteratorSplitAtExtension,
+		IteratorAccumulateExtension,
+		IteratorStepExtension,
+		IteratorGroupRunsExtension,
+		IteratorWindowsExtension,
+		IteratorElementwiseExtension,
+		IteratorTryReduceExtension,
+		IteratorRepeatEachExtension
+	};
+	#[cfg(feature="parallel")]
+	pub use super::ParallelIteratorTryReduceExtension;
+}
@@ -1,5 +1,7 @@
 //! 複数のイテレータのカーテジアン積をとるトレイトやイテレータをまとめたモジュール
 
+use super::*;
+
 /// イテレータのタプルに関してカーテジアン積をとる関数を含むモジュール
 pub mod for_iters_tuple {
 
@@ -7,7 +9,8 @@ pub mod for_iters_tuple {
 	pub struct CartesianProduct<I,O,V> {
 		pub(crate) iters_tuple: I,
 		pub(crate) iters_original_tuple: O,
-		pub(crate) current_val_tuple: Option<V>
+		pub(crate) current_val_tuple: Option<V>,
+		pub(crate) consumed_back: usize
 	}
 	use CartesianProduct as Product;
 
@@ -17,7 +20,7 @@ pub mod for_iters_tuple {
 		type CurrentValues;
 		type Iter;
 
-		/// イテレータのタプル `(I1,I2,I3,...)` をカーテジアン積をとったイテレータ `Iterator<Item=(T1,T2,T3,...)` に変換します。各イテレータが `Clone` を実装していなければなりません。
+		/// イテレータのタプル `(I1,I2,I3,...)` をカーテジアン積をとったイテレータ `Iterator<Item=(T1,T2,T3,...)` に変換します。各イテレータが `Clone` を実装していなければなりません。各イテレータが `DoubleEndedIterator` と `ExactSizeIterator` も実装していれば、逆方向からもイテレートできます。
 		fn cartesian_product(self) -> Product<Self,Self::OriginalIters,Self::CurrentValues>;
 	}
 
@@ -109,6 +112,7 @@ pub mod for_iters_tuple {
 					Self::Iter {
 						iters_original_tuple: (),
 						current_val_tuple: Some(()),
+						consumed_back: 0,
 						iters_tuple: self
 					}
 				}
@@ -134,6 +138,20 @@ pub mod for_iters_tuple {
 				}
 			}
 
+			impl<T,I> DoubleEndedIterator for Product<(I,),(),()>
+			where I: DoubleEndedIterator<Item=T>
+			{
+				fn next_back(&mut self) -> Option<Self::Item> {
+					self.iters_tuple.0.next_back()
+					.map(|v| (v,) )
+				}
+
+				fn nth_back(&mut self,n:usize) -> Option<Self::Item> {
+					self.iters_tuple.0.nth_back(n)
+					.map(|v| (v,) )
+				}
+			}
+
 		};
 
 		// イテレータが多数の場合の実装
@@ -159,6 +177,7 @@ pub mod for_iters_tuple {
 					Self::Iter {
 						iters_original_tuple: ((),$(self.$nml.clone()),+),
 						current_val_tuple: None,
+						consumed_back: 0,
 						iters_tuple: self
 					}
 				}
@@ -173,10 +192,14 @@ pub mod for_iters_tuple {
 				type Item = ($($ta),+);
 
 				fn next(&mut self) -> Option<Self::Item> {
+					// 逆方向からイテレートされている場合は、その位置を越えないようにする
+					if self.consumed_back>0 && self.size_hint().0==0 { return None; }
+
 					let Self {
 						iters_tuple: ref mut it,
 						iters_original_tuple: ref iot,
 						current_val_tuple: ref mut cvo,
+						..
 					} = self;
 
 					if let Some(cv) = cvo.as_mut() {
@@ -197,20 +220,70 @@ pub mod for_iters_tuple {
 					let Self {
 						iters_tuple: ref it,
 						iters_original_tuple: ref iot,
-						..
+						current_val_tuple: ref cvo,
+						consumed_back: ref b
 					} = self;
 
 					let mut ma = it.$nf.size_hint();
-					$( ma = size_hint_mul_add(
-						ma,
-						iot.$nml.size_hint(),
-						it.$nml.size_hint()
-					); )+
-					ma
+					// まだイテレートを始めていない場合は、各イテレータの要素数の積となる
+					if cvo.is_none() {
+						$( ma = size_hint_mul_add(
+							ma,
+							it.$nml.size_hint(),
+							(0,Some(0))
+						); )+
+					}
+					else {
+						$( ma = size_hint_mul_add(
+							ma,
+							iot.$nml.size_hint(),
+							it.$nml.size_hint()
+						); )+
+					}
+					(
+						ma.0.saturating_sub(*b),
+						ma.1.map(|u| u.saturating_sub(*b) )
+					)
 				}
 
 			}
 
+			impl<$($ta),+,$($ia),+> DoubleEndedIterator for Product<($($ia),+),((),$($iml),+),($($tfm),+,())>
+			where
+				$( $ia: DoubleEndedIterator<Item=$ta> + ExactSizeIterator + Clone ),+,
+				$( $tfm: Clone ),+
+			{
+				fn next_back(&mut self) -> Option<Self::Item> {
+					if self.size_hint().0==0 { return None; }
+
+					let Self {
+						iters_tuple: ref it,
+						iters_original_tuple: ref iot,
+						current_val_tuple: ref cvo,
+						consumed_back: ref mut b
+					} = self;
+
+					// 末尾から数えた位置を各イテレータの末尾から数えた位置に分解する
+					let l = [ 1 $(, iot.$nml.len() )+ ];
+					let mut r = [0_usize; $nl+1];
+					let mut q = *b;
+					for (r,l) in r.iter_mut().zip(l).skip(1).rev() {
+						*r = q % l;
+						q /= l;
+					}
+
+					// 先頭のイテレータは元の状態を保持していないため、残りの要素から取り出すか、現在の値を用いる
+					let v = match (it.$nf.len(),cvo) {
+						(l,_) if q<l => it.$nf.clone().nth_back(q)?,
+						(_,Some(cv)) => cv.$nf.clone(),
+						(_,None) => { return None; }
+					};
+					*b += 1;
+
+					Some( ( v $(, iot.$nml.clone().nth_back(r[$nml])? )+ ) )
+				}
+			}
+
 		};
 
 		// 関連トレイトの実装
@@ -260,6 +333,33 @@ pub mod for_iters_tuple {
 	}
 	pub(crate) use implement;
 
+	#[cfg(test)]
+	#[test]
+	fn test_double_ended() {
+		use crate::prelude::*;
+
+		let expected = (0..3).flat_map(|a| (0..2).flat_map(move |b| (0..4).map(move |c| (a,b,c) ) ) ).collect::<Vec<_>>();
+		let p = || (0..3,0..2,0..4).cartesian_product();
+		assert_eq!( p().len(), expected.len() );
+		assert_eq!( p().rev().collect::<Vec<_>>(), expected.iter().rev().cloned().collect::<Vec<_>>() );
+
+		// 両側から交互に取り出し、中央で出会ったときに全ての要素をちょうど1回ずつ訪れることを確かめる
+		for front in 0..=expected.len() {
+			let mut it = p();
+			let mut head = vec![];
+			let mut tail = vec![];
+			for _ in 0..front { head.push(it.next().unwrap()); }
+			while let Some(v) = it.next_back() {
+				tail.push(v);
+				assert_eq!( it.len(), expected.len()-head.len()-tail.len() );
+				if let Some(v) = it.next() { head.push(v); }
+			}
+			assert_eq!( it.next(), None );
+			head.extend(tail.into_iter().rev());
+			assert_eq!( head, expected );
+		}
+	}
+
 }
 
 
@@ -304,10 +404,9 @@ pub mod for_double_ended_iters_tuple {
 
 				implement! {@process $( $i $t $n )+ }
 
+				/// 残りの要素数: `forward_index` は前方から取り出した個数、 `backward_index` は全体の個数から後方から取り出した個数を引いたもの
 				fn length<I,O,V,L>(p:&Product<I,O,V,L>) -> usize {
-					p.backward_index
-					.checked_sub(p.forward_index)
-					.map_or(0,|d| d+1 )
+					p.backward_index.saturating_sub(p.forward_index)
 				}
 			}
 		};
@@ -784,6 +883,23 @@ pub mod for_double_ended_iters_tuple {
 				}
 			}
 
+			impl<$($i_fa),+,$($t_fa),+> Product<($($i_fa),+),(() $(,$i_fml)+),($($t_ffm,)+ ()),($($ua,)+)>
+			where
+				$( $i_fa: DoubleEndedIterator<Item=$t_fa> + ExactSizeIterator + Clone ),+ ,
+				$( $t_fa: Clone ),+
+			{
+				/// 前方から順に要素を調べ、 `pred` を満たす最初の要素の、各イテレータにおけるインデクスのタプルを返します。
+				/// * インデクスは、このイテレータの一部が既に取り出されていても、元のカーテジアン積の先頭を基準とした各イテレータでの位置になります。
+				/// * 見つかった要素までは取り出されます (`Iterator::position` と同様)。見つからない場合は `None` を返します。
+				pub fn position_nd<P>(&mut self,pred:P) -> Option<($($ua,)+)>
+				where P: FnMut(($($t_fa,)+)) -> bool
+				{
+					let start = self.forward_index;
+					let k = self.position(pred)?;
+					Some(self.index_each(start+k))
+				}
+			}
+
 			impl<$($i_fa),+,$($t_ffm),+> Product<($($i_fa),+),(() $(,$i_fml)+),($($t_ffm,)+ ()),($($ua,)+)>
 			{
 				fn index_each(&self,mut i:usize) -> ($($ua,)+) {
@@ -864,6 +980,188 @@ pub mod for_double_ended_iters_tuple {
 	}
 	pub(crate) use implement;
 
+	#[cfg(test)]
+	#[test]
+	fn test_len_after_random_advances() {
+		use crate::prelude::*;
+		use std::collections::VecDeque;
+
+		// 線形合同法による簡易な疑似乱数
+		let mut seed = 0x2545_f491_u64;
+		let mut rand = move |n:u64| {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((seed>>33)%n) as usize
+		};
+
+		for _ in 0..200 {
+			let mut p = (0..3,['a','b'].into_iter(),0..4).cartesian_product_double_ended();
+			let mut expected = (0..3).flat_map(|i| ['a','b'].into_iter().flat_map(move |c| (0..4).map(move |j| (i,c,j) ) ) ).collect::<VecDeque<_>>();
+			assert_eq!( p.len(), expected.len() );
+
+			while !expected.is_empty() {
+				let by = rand(4);
+				let (v,e) = match rand(4) {
+					0 => (p.next(),expected.pop_front()),
+					1 => (p.next_back(),expected.pop_back()),
+					2 => (p.nth(by),{ expected.drain(..by.min(expected.len())); expected.pop_front() }),
+					_ => (p.nth_back(by),{ let l = expected.len(); expected.drain(l-by.min(l)..); expected.pop_back() })
+				};
+				assert_eq!( v, e );
+				assert_eq!( p.len(), expected.len() );
+				assert_eq!( p.size_hint(), (expected.len(),Some(expected.len())) );
+				assert_eq!( p.clone().count(), expected.len() );
+			}
+			assert_eq!( p.next(), None );
+			assert_eq!( p.next_back(), None );
+			assert_eq!( p.len(), 0 );
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_position_nd() {
+		use crate::prelude::*;
+
+		let p = || (['a','b','c'].into_iter(),[10,20].into_iter(),0..4).cartesian_product_double_ended();
+		assert_eq!( p().position_nd(|(c,n,i)| c=='b' && n==20 && i==1 ), Some((1,1,1)) );
+		assert_eq!( p().position_nd(|(c,_,_)| c=='z' ), None );
+		assert_eq!( ([1,2,3].into_iter(),[4,5].into_iter()).cartesian_product_double_ended().position_nd(|(a,b)| a*b==10 ), Some((1,1)) );
+
+		// 一部を取り出した後でも、元の先頭を基準としたインデクスが得られる
+		let mut it = p();
+		it.nth(5);
+		assert_eq!( it.position_nd(|(_,_,i)| i==0 ), Some((1,0,0)) );
+		assert_eq!( it.position_nd(|(_,_,i)| i==0 ), Some((1,1,0)) );
+	}
+
+}
+
+
+
+/// イテレータの配列に関してカーテジアン積をとる関数を含むモジュール
+pub mod for_iters_array {
+	use super::*;
+
+	/// イテレータの配列のカーテジアン積をとったイテレータ
+	pub struct CartesianProduct<I:Iterator> {
+		iters: Vec<I>,
+		iters_original: Vec<I>,
+		current_vals: Option<Vec<I::Item>>
+	}
+	use CartesianProduct as Product;
+
+	/// 複数のイテレータの配列をカーテジアン積をとった単一のイテレータに変換するトレイト
+	pub trait IntoProduct<I:Iterator> {
+		/// イテレータの配列 `[I;N]` や `Vec<I>` などをカーテジアン積をとったイテレータ `Iterator<Item=Vec<T>>` に変換します。各イテレータが `Clone` を実装していなければなりません。
+		fn cartesian_product(self) -> Product<I>;
+	}
+	impl<II,I,T> IntoProduct<I> for II
+	where II: IntoIterator<Item=I>, I: Iterator<Item=T> + Clone
+	{
+		fn cartesian_product(self) -> Product<I> {
+			let iters = self.into_iter().collect::<Vec<_>>();
+			Product {
+				iters_original: iters.clone(),
+				current_vals: None,
+				iters
+			}
+		}
+	}
+
+	impl<I,T> Iterator for Product<I>
+	where I: Iterator<Item=T> + Clone, T: Clone
+	{
+		type Item = Vec<T>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			// 末尾に達した場合は iters を空にしている
+			if self.iters.is_empty() { return None; }
+
+			let Self {
+				iters: ref mut it,
+				iters_original: ref io,
+				current_vals: ref mut cvo
+			} = self;
+
+			// 先頭に位置する場合は別に処理する
+			let Some(cv) = cvo.as_mut() else {
+				match it.iter_mut().map(|i| i.next() ).collect::<Option<Vec<_>>>() {
+					Some(v) => {
+						*cvo = Some(v.clone());
+						return Some(v);
+					},
+					None => {
+						it.clear();
+						return None;
+					}
+				}
+			};
+
+			// 末尾の成分から順に進め、終わった成分は元に戻して1つ前の成分を進める
+			for k in (0..it.len()).rev() {
+				if let Some(v) = it[k].next() {
+					cv[k] = v;
+					return Some(cv.clone());
+				}
+				if k==0 { break; }
+				it[k] = io[k].clone();
+				cv[k] = it[k].next()?;
+			}
+			it.clear();
+			None
+		}
+
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			let Self {
+				iters: ref it,
+				iters_original: ref io,
+				current_vals: ref cvo
+			} = self;
+			if it.is_empty() { return (0,Some(0)); }
+
+			// まだイテレートを始めていない場合は各イテレータの要素数の積、そうでなければ残りの要素数を位取りで数える
+			let mut ma = it[0].size_hint();
+			for k in 1..it.len() {
+				let (a,b) = match cvo {
+					Some(_) => (io[k].size_hint(),it[k].size_hint()),
+					None => (it[k].size_hint(),(0,Some(0)))
+				};
+				ma = (
+					ma.0.saturating_mul(a.0).saturating_add(b.0),
+					ma.1
+					.zip(a.1).and_then(|(m,a)| m.checked_mul(a) )
+					.zip(b.1).and_then(|(m,b)| m.checked_add(b) )
+				);
+			}
+			ma
+		}
+	}
+
+	impl<I,T> ExactSizeIterator for Product<I>
+	where I: ExactSizeIterator<Item=T> + Clone, T: Clone {}
+
+	impl<I,T> FusedIterator for Product<I>
+	where I: Iterator<Item=T> + Clone, T: Clone {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_product_array() {
+		let p = vec![0..2_u32,0..3,0..4].cartesian_product();
+		assert_eq!( p.len(), 24 );
+
+		let expected = (0..2).flat_map(|a| (0..3).flat_map(move |b| (0..4).map(move |c| vec![a,b,c] ) ) ).collect::<Vec<_>>();
+		let mut p = vec![0..2_u32,0..3,0..4].cartesian_product();
+		let mut visited = vec![];
+		while let Some(v) = p.next() {
+			visited.push(v);
+			assert_eq!( p.len(), expected.len()-visited.len() );
+		}
+		assert_eq!( visited, expected );
+		assert_eq!( p.next(), None );
+
+		assert_eq!( vec![0..2_u32,0..0,0..4].cartesian_product().count(), 0 );
+	}
+
 }
 
 
@@ -872,6 +1170,7 @@ pub mod for_double_ended_iters_tuple {
 pub(crate) mod for_prelude {
 	pub use super::{
 		for_iters_tuple::IntoProduct as IntoProductForIterators,
-		for_double_ended_iters_tuple::IntoProduct as IntoDoubleEndedProductForIterators
+		for_double_ended_iters_tuple::IntoProduct as IntoDoubleEndedProductForIterators,
+		for_iters_array::IntoProduct as IntoArrayProductIterator
 	};
 }
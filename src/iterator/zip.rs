@@ -15,6 +15,12 @@ pub mod for_iters {
 		fn zip_eq(self) -> ZipEq<Self>;
 	}
 
+	/// 可変参照で借用した複数のイテレータのタプルを、元のイテレータを消費せずにタプルのイテレータに変換するトレイト
+	pub trait IntoZipRef: Sized {
+		/// 可変参照のタプル `(&mut I1,&mut I2,&mut I3,...)` をタプルのイテレータ `Iterator<Item=(T1,T2,T3,...)>` に変換します。途中まで走査した後も、元のイテレータから残りの要素を取り出せます。
+		fn zip_ref(self) -> Zip<Self>;
+	}
+
 	pub trait IntoZipLongest: Sized {
 		type Item;
 		type Iter;
@@ -22,11 +28,30 @@ pub mod for_iters {
 		fn zip_longest(self,default:Self::Item) -> Self::Iter;
 	}
 
+	/// 複数のイテレータのタプルを、取り出した要素を捨てずに zip するトレイト
+	pub trait IntoZipNoWaste: Sized {
+		/// 取り出したが使われなかった要素を保持するタプル `(Option<T1>,Option<T2>,...)`
+		type Leftovers;
+		/// イテレータのタプル `(I1,I2,I3,...)` をタプルのイテレータ `Iterator<Item=(T1,T2,T3,...)>` に変換します。
+		/// * いずれかのイテレータが末尾に達した際に、他のイテレータから取り出してしまった要素を保持しておき、 `into_remainders()` で残りの要素と共に取り出せます。
+		/// * 保持のため、各イテレータにつき `Option<T>` の領域が必要になります。また、途中のイテレータが末尾に達しても、残りの全てのイテレータから1つずつ要素を取り出します。
+		fn zip_no_waste(self) -> ZipNoWaste<Self,Self::Leftovers>;
+	}
+
 	/// 複数のイテレータを単一のイテレータに zip したイテレータ
 	pub struct Zip<I> {
 		pub(crate) iters: I
 	}
 
+	impl<I> Zip<I> where Self: Iterator {
+		/// 各タプル `(T1,T2,T3,...)` に対して失敗しうる処理 `f` を順に実行します。
+		/// * `f` が `Err` を返した時点で走査を打ち切り、その `Err` を返します。全て `Ok` の場合は `Ok(())` を返します。
+		/// * `Iterator::try_for_each` と同等です。
+		pub fn try_for_each_zip<F,E>(mut self,f:F) -> Result<(),E>
+		where F: FnMut(<Self as Iterator>::Item) -> Result<(),E>
+		{ self.try_for_each(f) }
+	}
+
 	/// 複数のイテレータを単一のイテレータに zip したイテレータ。要素数が一致していないとパニックを発する。
 	pub struct ZipEq<I> {
 		pub(crate) iters: I
@@ -38,6 +63,12 @@ pub mod for_iters {
 		pub(crate) values: V
 	}
 
+	/// 複数のイテレータを単一のイテレータに zip したイテレータ。いずれかのイテレータが末尾に達した際に、他のイテレータから取り出した要素を捨てずに保持する。
+	pub struct ZipNoWaste<I,V> {
+		pub(crate) iters: I,
+		pub(crate) leftovers: V
+	}
+
 	/// * イテレータの要素数ごとに `Zip` を実装するマクロ
 	/// * `implement!( T0 0 T1 1 T2 2 ... T(N-1) (N-1) )` と指定すれば、 `N` 個の要素まで対応する
 	/// * 異なる型パラメータとタプルのインデクスを交互に並べる
@@ -80,6 +111,14 @@ pub mod for_iters {
 				}
 			}
 
+			// `&mut I` も `Iterator` なので、 `Zip` の実装はそのまま使える
+			impl<'a,$($i),+> IntoZipRef for ($(&'a mut $i,)+)
+			where $( $i: Iterator ),+ {
+				fn zip_ref(self) -> Zip<Self> {
+					Zip { iters: self }
+				}
+			}
+
 			impl<$($i),+,$($t),+> IntoZipLongest for ($($i,)+)
 			where $( $i: Iterator<Item=$t>, $t: Clone ),+ {
 				type Item = ( $( $t, )+ );
@@ -93,6 +132,55 @@ pub mod for_iters {
 				}
 			}
 
+			impl<$($i),+,$($t),+> IntoZipNoWaste for ($($i,)+)
+			where $( $i: Iterator<Item=$t> ),+ {
+				type Leftovers = ( $( Option<$t>, )+ );
+
+				fn zip_no_waste(self) -> ZipNoWaste<Self,Self::Leftovers> {
+					ZipNoWaste {
+						iters: self,
+						leftovers: implement!{@repeat $( $n None )+ }
+					}
+				}
+			}
+
+			impl<$($i),+,$($t),+> Iterator for ZipNoWaste<($($i,)+),($(Option<$t>,)+)>
+			where $( $i: Iterator<Item=$t> ),+
+			{
+				type Item = ( $( $t, )+ );
+
+				fn next(&mut self) -> Option<Self::Item> {
+					let v = ( $( self.leftovers.$n.take().or_else(|| self.iters.$n.next() ), )+ );
+					if $( v.$n.is_some() )&&+ {
+						return v.zip_options();
+					}
+					// 揃わなかった場合は、取り出した要素を次回以降のために保持する
+					self.leftovers = v;
+					None
+				}
+
+				fn size_hint(&self) -> (usize, Option<usize>) {
+					let size_hint = ( $( {
+						let (l,u) = self.iters.$n.size_hint();
+						let k = self.leftovers.$n.is_some() as usize;
+						(l.saturating_add(k),u.and_then(|u| u.checked_add(k) ))
+					}, )+ );
+					let l = [ $( size_hint.$n.0 ),+ ].minimum();
+					let u = [ $( size_hint.$n.1 ),+ ].iter().filter_map(|x| x.as_ref()).min().map(|x| *x);
+					(l,u)
+				}
+			}
+
+			impl<$($i),+,$($t),+> ZipNoWaste<($($i,)+),($(Option<$t>,)+)>
+			where $( $i: Iterator<Item=$t> ),+
+			{
+				/// 各イテレータの残りの要素を、保持していた要素を先頭に付けたイテレータのタプルとして返します
+				pub fn into_remainders(self) -> ( $( std::iter::Chain<std::option::IntoIter<$t>,$i>, )+ ) {
+					let Self { iters, leftovers } = self;
+					( $( leftovers.$n.into_iter().chain(iters.$n), )+ )
+				}
+			}
+
 			impl<$($i),+,$($t),+> Iterator for Zip<($($i,)+)>
 			where $( $i: Iterator<Item=$t> ),+
 			{
@@ -125,14 +213,14 @@ pub mod for_iters {
 				fn next(&mut self) -> Option<Self::Item> {
 					implement!{@zip_eq
 						target( ( $(self.iters.$n.next(), )+ ) )
-						indices($($i)+)
+						indices($($n)+)
 					}
 				}
 
 				fn nth(&mut self,n:usize) -> Option<Self::Item> {
 					implement!{@zip_eq
 						target( ( $(self.iters.$n.nth(n), )+ ) )
-						indices($($i)+)
+						indices($($n)+)
 					}
 				}
 
@@ -360,6 +448,7 @@ pub mod for_iters {
 			}
 		};
 		// `ZipEq` の `.next()` や `.nth()` の条件分岐: 最後に呼び出され、組み立てる
+		// 空になったイテレータが複数あれば、最も小さいインデクスを報告する
 		(@zip_eq
 			target($($t:tt)+)
 			all_some($($s:tt)+)
@@ -371,7 +460,7 @@ pub mod for_iters {
 				p @ ($($s)+) => p.zip_options(),
 				($($n)+) => None,
 				$( ($($o)+) => {
-					panic!(concat!("インデクス ",stringify!($i)," の要素が空になりました"));
+					panic!(concat!("インデクス ",stringify!($i)," のイテレータが他より先に空になりました"));
 				}, )+
 			}
 		};
@@ -383,6 +472,94 @@ pub mod for_iters {
 	}
 	pub(crate) use implement;
 
+	#[cfg(test)]
+	#[test]
+	fn test_zip_eq_panic() {
+		use crate::prelude::*;
+		use std::panic::{catch_unwind,AssertUnwindSafe};
+
+		fn message(f:impl FnOnce()) -> String {
+			let e = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+			e.downcast_ref::<&str>().unwrap().to_string()
+		}
+
+		assert_eq!( (0..3,0..3,0..3).zip_eq().count(), 3 );
+		assert_eq!(
+			message(|| { (0..3,0..2,0..3).zip_eq().for_each(drop); }),
+			"インデクス 1 のイテレータが他より先に空になりました"
+		);
+		assert_eq!(
+			message(|| { (0..3,0..3,0..1).zip_eq().for_each(drop); }),
+			"インデクス 2 のイテレータが他より先に空になりました"
+		);
+		assert_eq!(
+			message(|| { (0..1,0..2,0..1).zip_eq().for_each(drop); }),
+			"インデクス 0 のイテレータが他より先に空になりました"
+		);
+		// イテレータの要素数が不明でも、走査中に検出できる
+		assert_eq!(
+			message(|| { ((0..5).filter(|i| i%2==0 ),0..3,0..2).zip_eq().for_each(drop); }),
+			"インデクス 2 のイテレータが他より先に空になりました"
+		);
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_zip_ref() {
+		use crate::prelude::*;
+
+		let mut a = 0..5;
+		let mut b = vec!['a','b','c','d','e','f'].into_iter();
+		let z = (&mut a,&mut b).zip_ref();
+		assert_eq!( z.size_hint(), (5,Some(5)) );
+		assert_eq!( z.take(2).collect::<Vec<_>>(), vec![(0,'a'),(1,'b')] );
+		// 元のイテレータは途中まで消費された状態で再利用できる
+		assert_eq!( a.clone().collect::<Vec<_>>(), vec![2,3,4] );
+		assert_eq!( (&mut a,&mut b).zip_ref().last(), Some((4,'e')) );
+		assert_eq!( b.collect::<String>(), "f" );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_zip_no_waste() {
+		use crate::prelude::*;
+
+		let mut z = (0..5,vec!['a','b'].into_iter(),10..13).zip_no_waste();
+		assert_eq!( z.size_hint(), (2,Some(2)) );
+		assert_eq!( z.by_ref().collect::<Vec<_>>(), vec![(0,'a',10),(1,'b',11)] );
+		// 2つ目のイテレータが先に末尾に達しても、1つ目と3つ目から取り出した要素は失われない
+		assert_eq!( z.size_hint(), (0,Some(0)) );
+		assert_eq!( z.next(), None );
+		let (a,b,c) = z.into_remainders();
+		assert_eq!( a.collect::<Vec<_>>(), vec![2,3,4] );
+		assert_eq!( b.count(), 0 );
+		assert_eq!( c.collect::<Vec<_>>(), vec![12] );
+
+		// 通常の `zip` では取り出された要素が失われる
+		let mut a = 0..5;
+		let mut b = vec!['a','b'].into_iter();
+		assert_eq!( (&mut a,&mut b).zip().count(), 2 );
+		assert_eq!( a.collect::<Vec<_>>(), vec![3,4] );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_try_for_each_zip() {
+		use crate::prelude::*;
+
+		let mut sum = 0;
+		let r = (1..4,vec![10,20,30].into_iter()).zip().try_for_each_zip(|(a,b)| { sum += a*b; Ok::<(),String>(()) });
+		assert_eq!( (r,sum), (Ok(()),140) );
+
+		let mut visited = vec![];
+		let r = (0..10,(0..10).rev()).zip().try_for_each_zip(|(a,b)| {
+			if a>b { return Err(a); }
+			visited.push(a);
+			Ok(())
+		});
+		assert_eq!( (r,visited), (Err(5),vec![0,1,2,3,4]) );
+	}
+
 }
 
 
@@ -414,17 +591,44 @@ pub mod for_parallel_iters {
 		fn parallel_zip(self) -> Zip<Self::ItersTuple>;
 	}
 
+	/// 複数の並列化可能なアイテムから、最も長いものに合わせて並列化したタプルのイテレータに変換するトレイト
+	pub trait IntoParallelZipLongest: Sized {
+		type ItersTuple;
+		type Item;
+		/// 並列化可能なアイテムのタプル `(I1,I2,I3,...)` を、それぞれ `into_par_iter()` で変換してから最も長いイテレータに合わせて zip した並列イテレータ `ParallelIterator<Item=(T1,T2,T3,...)>` に変換します。先に空になったイテレータの要素は `default` の対応する要素の複製で埋められます。
+		fn parallel_zip_longest(self,default:Self::Item) -> ZipLongest<Self::ItersTuple,Self::Item>;
+	}
+
 	/// 複数の並列イテレータを単一のイテレータに zip した並列イテレータ
 	pub struct Zip<I> {
 		pub(crate) iters: I
 	}
 
+	impl<I> Zip<I> where Self: rayon::iter::ParallelIterator {
+		/// 各タプル `(T1,T2,T3,...)` に対して失敗しうる処理 `f` を並列に実行します。
+		/// * いずれかの `f` が `Err` を返すと、まだ開始していない処理を打ち切り、その `Err` を返します。全て `Ok` の場合は `Ok(())` を返します。
+		/// * 複数の `Err` が生じた場合にどれが返されるかは、実行順序に依存します。
+		/// * `ParallelIterator::try_for_each` と同等です。
+		pub fn try_for_each_zip<F,E>(self,f:F) -> Result<(),E>
+		where
+			F: Fn(<Self as rayon::iter::ParallelIterator>::Item) -> Result<(),E> + Sync + Send,
+			E: Send
+		{ rayon::iter::ParallelIterator::try_for_each(self,f) }
+	}
+
 	/// 複数の並列イテレータを単一のイテレータに zip した並列イテレータ。要素数が一致しない場合は、デフォルト値を返す。
 	pub struct ZipLongest<I,V> {
 		pub(crate) iters: I,
 		pub(crate) values: V
 	}
 
+	/// 並列イテレータのタプル `(I1,I2,I3,...)` を、最も長いイテレータに合わせて zip した並列イテレータ `ParallelIterator<Item=(T1,T2,T3,...)>` を生成します。
+	/// * 先に空になったイテレータの要素は `default` の対応する要素の複製で埋められます。
+	/// * `iters.zip_longest(default)` と同等です。
+	pub fn par_zip_longest<I>(iters:I,default:I::Item) -> ZipLongest<I,I::Item>
+	where I: IntoZipLongest
+	{ iters.zip_longest(default) }
+
 	pub(crate) struct ZipCallback<CCB,PIT> {
 		pub(crate) child_callback: CCB,
 		/// * `( (P0,), (P1,), ..., (Pk-1,), (), (Ik+1,), ..., (In,) )` の形式で管理する
@@ -436,9 +640,11 @@ pub mod for_parallel_iters {
 	}
 
 	use std::sync::Arc;
-	pub(crate) struct ZipLongestProducer<P,V> {
+	pub(crate) struct ZipLongestProducer<P,V,const N:usize> {
 		pub(crate) producers: P,
-		pub(crate) values: Arc<V>
+		pub(crate) values: Arc<V>,
+		/// * 各プロデューサの要素数。短いプロデューサを範囲外で分割しないために用いる
+		pub(crate) lens: [usize;N]
 	}
 
 	/// * イテレータの要素数ごとに `Zip` を実装するマクロ
@@ -506,6 +712,16 @@ pub mod for_parallel_iters {
 				}
 			}
 
+			impl<$($i),+,$($t),+> IntoParallelZipLongest for ($($i,)+)
+			where $( $i: IntoParallelIterator<Item=$t>, $i::Iter: IndexedParallelIterator, $t: Clone + Send + Sync ),+
+			{
+				type ItersTuple = ( $($i::Iter,)+ );
+				type Item = ($($t,)+);
+				fn parallel_zip_longest(self,default:Self::Item) -> ZipLongest<Self::ItersTuple,Self::Item> {
+					ZipLongest { iters: ( $( self.$n.into_par_iter(), )+ ), values: default }
+				}
+			}
+
 			impl<$($i),+,$($t),+> IntoParallelIterator for ZipSerial<($($i,)+)>
 			where
 				$( $i: IntoParallelIterator + Iterator<Item=$t>, $t: Send, )+
@@ -593,7 +809,7 @@ pub mod for_parallel_iters {
 
 			}
 
-			impl<$($p),+,$($t),+> Producer for ZipLongestProducer<($($p,)+),($($t,)+)>
+			impl<$($p),+,$($t),+,const N:usize> Producer for ZipLongestProducer<($($p,)+),($($t,)+),N>
 			where $( $p: Producer<Item=$t>, $t: Clone + Send + Sync ),+
 			{
 				type Item = ($($t,)+);
@@ -617,15 +833,18 @@ pub mod for_parallel_iters {
 				}
 
 				fn split_at(self, index: usize) -> (Self, Self) {
-					let split_prod = ( $( self.producers.$n.split_at(index), )+ );
+					// 短いプロデューサは自身の要素数を超えて分割できないので、分割位置を要素数で制限する
+					let split_prod = ( $( self.producers.$n.split_at(index.min(self.lens[$n])), )+ );
 					(
 						Self {
 							producers: ( $( split_prod.$n.0, )+ ),
-							values: self.values.clone()
+							values: self.values.clone(),
+							lens: self.lens.map(|l| l.min(index) )
 						},
 						Self {
 							producers: ( $( split_prod.$n.1, )+ ),
-							values: self.values
+							values: self.values,
+							lens: self.lens.map(|l| l.saturating_sub(index) )
 						}
 					)
 				}
@@ -697,12 +916,14 @@ pub mod for_parallel_iters {
 				fn with_producer<CCB>(self, child_callback: CCB) -> CCB::Output
 				where CCB: ProducerCallback<Self::Item>
 				{
+					// デフォルト値と共に各イテレータの要素数を受け渡す
+					let lens = ( self.iters.$n.len(), $( self.iters.$nf.len(), )* );
 					self.iters.$n
 					.with_producer(ZipCallback {
 						child_callback,
 						prods_iters: (
-							(self.values.$n,),
-							$( (self.values.$nf,self.iters.$nf), )*
+							((self.values.$n,lens.$n),),
+							$( ((self.values.$nf,lens.$nf),self.iters.$nf), )*
 						)
 					})
 				}
@@ -749,7 +970,7 @@ pub mod for_parallel_iters {
 
 			impl< CCB $(,$pp)*, $in$(,$if)*, $($tp,)*$t,$tn$(,$tf)* >
 			ProducerCallback<$t>
-			for ZipCallback<CCB,( $(($tp,$pp),)* ($t,), ($tn,$in) $(,($tf,$if))* )>
+			for ZipCallback<CCB,( $((($tp,usize),$pp),)* (($t,usize),), (($tn,usize),$in) $(,(($tf,usize),$if))* )>
 			where
 				CCB: ProducerCallback<($($tp,)*$t,$tn$(,$tf)*)>,
 				$( $pp: Producer<Item=$tp>, )*
@@ -809,7 +1030,7 @@ pub mod for_parallel_iters {
 				}
 			}
 
-			impl< CCB, $($pp,)* $($tp,)*$t > ProducerCallback<$t> for ZipCallback<CCB, ( $( ($tp,$pp), )* ($t,), ) >
+			impl< CCB, $($pp,)* $($tp,)*$t > ProducerCallback<$t> for ZipCallback<CCB, ( $( (($tp,usize),$pp), )* (($t,usize),), ) >
 			where
 				CCB: ProducerCallback<($($tp,)*$t,)>,
 				$( $pp: Producer<Item=$tp>, )*
@@ -823,9 +1044,13 @@ pub mod for_parallel_iters {
 					self.child_callback
 					.callback( ZipLongestProducer {
 						values: Arc::new( (
-							$( self.prods_iters.$np.0, )*
-							self.prods_iters.$n.0,
+							$( self.prods_iters.$np.0.0, )*
+							self.prods_iters.$n.0.0,
 						) ),
+						lens: [
+							$( self.prods_iters.$np.0.1, )*
+							self.prods_iters.$n.0.1
+						],
 						producers: (
 							$( self.prods_iters.$np.1, )*
 							parent_producer,
@@ -838,6 +1063,56 @@ pub mod for_parallel_iters {
 	}
 	pub(crate) use implement;
 
+
+	#[cfg(test)]
+	#[test]
+	fn test_par_zip_longest() {
+		use crate::prelude::*;
+		use rayon::iter::{IntoParallelIterator,ParallelIterator};
+
+		let v: Vec<_> = par_zip_longest( ((0..3).into_par_iter(),(10..15).into_par_iter()), (-1,-1) ).collect();
+		assert_eq!( v, vec![(0,10),(1,11),(2,12),(-1,13),(-1,14)] );
+
+		let v: Vec<_> = par_zip_longest( (vec!['a'].into_par_iter(),(0..3).into_par_iter(),(0..2).into_par_iter()), ('z',0,0) ).collect();
+		assert_eq!( v, vec![('a',0,0),('z',1,1),('z',2,0)] );
+
+		// 分割が十分に行われる長さでも、短いイテレータはデフォルト値で埋められる
+		let v: Vec<_> = par_zip_longest( ((0..1000).into_par_iter(),(0..10).into_par_iter()), (0,-1) ).collect();
+		assert_eq!( v, (0..1000).map(|i| (i,if i<10 {i} else {-1}) ).collect::<Vec<_>>() );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_parallel_try_for_each_zip() {
+		use rayon::iter::IntoParallelIterator;
+		use std::sync::atomic::{AtomicUsize,Ordering};
+
+		let sum = AtomicUsize::new(0);
+		let r = ((0..1000_usize).into_par_iter(),vec![2_usize;1000].into_par_iter()).zip().try_for_each_zip(|(a,b)| {
+			sum.fetch_add(a*b,Ordering::Relaxed);
+			Ok::<(),usize>(())
+		});
+		assert_eq!( (r,sum.into_inner()), (Ok(()),999*1000) );
+
+		let r = ((0..1000_usize).into_par_iter(),(0..1000_usize).into_par_iter()).zip().try_for_each_zip(|(a,b)| {
+			if a+b>=1000 { Err(a) } else { Ok(()) }
+		});
+		assert!( matches!(r,Err(a) if a>=500) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_parallel_zip_longest() {
+		use crate::prelude::*;
+		use rayon::iter::ParallelIterator;
+
+		let a: Vec<i32> = (0..700).map(|i| i*3 ).collect();
+		let b = 0..1200_usize;
+		let parallel: Vec<_> = (a.clone(),b.clone()).parallel_zip_longest((-1,0)).collect();
+		let serial: Vec<_> = (a.into_iter(),b).zip_longest((-1,0)).collect();
+		assert_eq!( parallel.len(), 1200 );
+		assert_eq!( parallel, serial );
+	}
 }
 
 
@@ -1019,6 +1294,8 @@ pub(crate) mod for_prelude {
 	pub use super::{
 		for_iters::{
 			IntoZip as IntoZipForIterators,
+			IntoZipRef as IntoZipRefForIterators,
+			IntoZipNoWaste as IntoZipNoWasteForIterators,
 			IntoZipLongest as IntoZipLongestForIterators
 		},
 		for_iters_array::
@@ -1027,6 +1304,8 @@ pub(crate) mod for_prelude {
 	#[cfg(feature="parallel")]
 	pub use super::for_parallel_iters::{
 		IntoZipLongest as IntoZipLongestForParallelIterators,
-		IntoParallelZip as IntoZipForParallelIteratorsFromSerial
+		par_zip_longest,
+		IntoParallelZip as IntoZipForParallelIteratorsFromSerial,
+		IntoParallelZipLongest as IntoZipLongestForParallelIteratorsFromSerial
 	};
 }
@@ -5,13 +5,314 @@ use std::{
 	process::exit
 };
 
-/// Result 型や Option 型をアンラップして、エラーをログとして出力する
-mod unwrap_result_option {
+
+
+/// ログの重要度と、出力するログの重要度の閾値
+mod level {
+	use std::sync::atomic::{AtomicU8,Ordering};
+
+	#[cfg(feature="logging")]
+	extern crate log;
+
+	/// ログの重要度 (`log` クレートと同様に、重要なものほど小さい)
+	#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash)]
+	#[repr(u8)]
+	pub enum LogLevel {
+		Error = 1,
+		Warn,
+		Info,
+		Debug,
+		Trace,
+	}
+
+	impl LogLevel {
+		/// 全ての重要度 (重要なものから順に並ぶ)
+		pub const ALL: [LogLevel;5] = [Self::Error,Self::Warn,Self::Info,Self::Debug,Self::Trace];
+
+		/// 出力時に付けるラベル
+		pub fn label(self) -> &'static str {
+			match self {
+				Self::Error => "ERROR",
+				Self::Warn  => "WARNING",
+				Self::Info  => "INFO",
+				Self::Debug => "DEBUG",
+				Self::Trace => "TRACE",
+			}
+		}
+
+		#[cfg(feature="logging")]
+		/// `log` クレートの重要度に変換します
+		pub fn to_log_level(self) -> log::Level {
+			match self {
+				Self::Error => log::Level::Error,
+				Self::Warn  => log::Level::Warn,
+				Self::Info  => log::Level::Info,
+				Self::Debug => log::Level::Debug,
+				Self::Trace => log::Level::Trace,
+			}
+		}
+	}
+
+	/// コンパイル時に決まる、出力するログの重要度の上限
+	/// * `max_level_error`, `max_level_warn`, `max_level_info`, `max_level_debug` フィーチャーにより指定します (複数指定した場合は最も厳しいものになります)。
+	/// * 指定しない場合は全てのログを出力します。
+	/// * この上限を超えるログは `log_at!` マクロの分岐が定数により除去されるので、実行時のコストがかかりません。
+	pub const STATIC_MAX_LEVEL: LogLevel = {
+		if cfg!(feature="max_level_error") { LogLevel::Error }
+		else if cfg!(feature="max_level_warn") { LogLevel::Warn }
+		else if cfg!(feature="max_level_info") { LogLevel::Info }
+		else if cfg!(feature="max_level_debug") { LogLevel::Debug }
+		else { LogLevel::Trace }
+	};
+
+	/// 実行時に変更可能な、出力するログの重要度の上限
+	static MAX_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Trace as u8);
+
+	/// 出力するログの重要度の上限を実行時に設定します。 `level` より重要度が低いログは出力されなくなります。
+	/// * `STATIC_MAX_LEVEL` を超えるログは、この設定によらず出力されません。
+	pub fn set_max_level(level:LogLevel) {
+		MAX_LEVEL.store(level as u8,Ordering::Relaxed);
+	}
+
+	/// 現在の出力するログの重要度の上限を返します。
+	pub fn max_level() -> LogLevel {
+		let l = MAX_LEVEL.load(Ordering::Relaxed);
+		LogLevel::ALL.into_iter().find(|v| *v as u8==l ).unwrap_or(LogLevel::Trace).min(STATIC_MAX_LEVEL)
+	}
+
+	/// 重要度 `level` のログが出力されるかどうかを返します。
+	#[inline]
+	pub fn log_enabled(level:LogLevel) -> bool {
+		level<=STATIC_MAX_LEVEL && level as u8<=MAX_LEVEL.load(Ordering::Relaxed)
+	}
+
+	/// 重要度 `level` のログとしてメッセージを出力します。上限を超える重要度の場合は何もしません。
+	/// * `set_sink` で出力先が設定されていればそこに、そうでなければ `logging` フィーチャーが有効な場合は `log` クレートに、無効な場合は標準エラー出力に出力します。
+	pub fn emit_log(level:LogLevel,message:&str) {
+		emit_log_with_fields(level,message,&[]);
+	}
+
+	/// `emit_log` と同様ですが、キーと値の組からなる構造化されたフィールド `fields` を付けてログを出力します。
+	/// * フィールドの書式は出力先が決めます。既定では `メッセージ key=value ...` の形式で出力します。
+	pub fn emit_log_with_fields(level:LogLevel,message:&str,fields:&[(&'static str,String)]) {
+		if !log_enabled(level) { return; }
+		super::sink::dispatch(level,message,fields);
+	}
+
+	/// テストで全体の設定 (重要度の上限や出力先) を変更する際に、他のテストと干渉しないようにするためのロック
+	#[cfg(test)]
+	pub(crate) static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+	#[macro_export]
+	/// 重要度を指定してログを出力します。
+	/// * `log_at!(Info,"{} 件読み込みました",n)` のように、重要度を `LogLevel` のバリアント名で指定し、続けて `format!` と同じ引数を与えます。
+	/// * `STATIC_MAX_LEVEL` を超える重要度の場合は、分岐が定数により除去され、引数は評価されません。
+	macro_rules! log_at {
+		( $level:ident, $($arg:tt)+ ) => {
+			if $crate::LogLevel::$level<=$crate::STATIC_MAX_LEVEL && $crate::log_enabled($crate::LogLevel::$level) {
+				$crate::emit_log($crate::LogLevel::$level,&format!($($arg)+));
+			}
+		};
+	}
+
+	#[macro_export]
+	/// 重要度を指定して、キーと値の組からなるフィールドを付けたログを出力します。
+	/// * `log_kv!(Info,"loaded",count = n,path = p)` のように、重要度、メッセージ、 `キー = 値` の組を与えます。値は `Display` を実装する必要があります。
+	/// * `log_at!` と同様に、 `STATIC_MAX_LEVEL` を超える重要度の場合は引数は評価されません。
+	macro_rules! log_kv {
+		( $level:ident, $message:expr $( , $key:ident = $value:expr )* $(,)? ) => {
+			if $crate::LogLevel::$level<=$crate::STATIC_MAX_LEVEL && $crate::log_enabled($crate::LogLevel::$level) {
+				$crate::emit_log_with_fields(
+					$crate::LogLevel::$level,
+					&format!("{}",$message),
+					&[ $( (stringify!($key),format!("{}",$value)) ),* ]
+				);
+			}
+		};
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_max_level() {
+		let _lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner() );
+		assert!( LogLevel::Error<LogLevel::Warn && LogLevel::Debug<LogLevel::Trace );
+
+		set_max_level(LogLevel::Error);
+		assert_eq!( max_level(), LogLevel::Error );
+		assert!( log_enabled(LogLevel::Error) );
+		assert!( !log_enabled(LogLevel::Warn) && !log_enabled(LogLevel::Trace) );
+
+		// 上限を超えるログは引数も評価されない
+		let mut evaluated = false;
+		crate::log_at!(Info,"{}",{ evaluated = true; 0 });
+		assert!( !evaluated );
+		crate::log_at!(Error,"{}",{ evaluated = true; 0 });
+		assert!( evaluated );
+
+		set_max_level(LogLevel::Trace);
+		assert_eq!( max_level(), STATIC_MAX_LEVEL );
+	}
+
+}
+pub use level::{LogLevel,STATIC_MAX_LEVEL,set_max_level,max_level,log_enabled,emit_log,emit_log_with_fields};
+
+
+
+/// ログの出力先を差し替える仕組み
+mod sink {
 	use super::*;
+	use std::sync::{Arc,Mutex,RwLock};
 
 	#[cfg(feature="logging")]
 	extern crate log;
 
+	/// ログの出力先を表すトレイト
+	pub trait LogSink: Send + Sync {
+		/// 重要度 `level` のメッセージ `message` を1件書き込みます
+		fn write_record(&self,level:LogLevel,message:&str);
+		/// 重要度 `level` のメッセージ `message` を、キーと値の組からなるフィールド `fields` と共に1件書き込みます
+		/// * 既定では `format_fields` で1つのメッセージにまとめて `write_record` に渡します。フィールドを独自に扱う場合は上書きします。
+		fn write_record_with_fields(&self,level:LogLevel,message:&str,fields:&[(&'static str,String)]) {
+			if fields.is_empty() { self.write_record(level,message); }
+			else { self.write_record(level,&format_fields(message,fields)); }
+		}
+	}
+
+	/// メッセージとフィールドを `メッセージ key=value ...` の形式の文字列にまとめます
+	pub fn format_fields(message:&str,fields:&[(&'static str,String)]) -> String {
+		fields.iter().fold(message.to_string(),|mut s,(k,v)| {
+			s.push_str(&format!(" {}={}",k,v));
+			s
+		})
+	}
+
+	/// フィールドを含むログの記録
+	#[derive(Debug,Clone,PartialEq,Eq)]
+	pub struct LogRecord {
+		pub level: LogLevel,
+		pub message: String,
+		pub fields: Vec<(&'static str,String)>
+	}
+
+	/// 標準エラー出力にログを書き込む出力先
+	#[derive(Debug,Clone,Copy,Default)]
+	pub struct StderrSink;
+
+	impl LogSink for StderrSink {
+		fn write_record(&self,level:LogLevel,message:&str) {
+			eprintln!("{}: {}",level.label(),message);
+		}
+	}
+
+	/// 書き込まれたログをメモリ上に蓄える出力先 (主にテスト用)
+	/// * 複製しても同じ記録を共有するので、複製を `set_sink` に渡し、元の値から記録を読み出せます。
+	#[derive(Debug,Clone,Default)]
+	pub struct VecSink {
+		records: Arc<Mutex<Vec<LogRecord>>>
+	}
+
+	impl VecSink {
+		pub fn new() -> Self { Self::default() }
+		/// これまでに書き込まれたログの重要度とメッセージを、書き込まれた順に返します
+		pub fn records(&self) -> Vec<(LogLevel,String)> {
+			self.full_records().into_iter().map(|r| (r.level,r.message) ).collect()
+		}
+		/// これまでに書き込まれたログを、フィールドも含めて書き込まれた順に返します
+		pub fn full_records(&self) -> Vec<LogRecord> {
+			self.records.lock().unwrap_or_else(|e| e.into_inner() ).clone()
+		}
+		/// これまでに書き込まれたログを消去します
+		pub fn clear(&self) {
+			self.records.lock().unwrap_or_else(|e| e.into_inner() ).clear();
+		}
+	}
+
+	impl LogSink for VecSink {
+		fn write_record(&self,level:LogLevel,message:&str) {
+			self.write_record_with_fields(level,message,&[]);
+		}
+		fn write_record_with_fields(&self,level:LogLevel,message:&str,fields:&[(&'static str,String)]) {
+			self.records.lock().unwrap_or_else(|e| e.into_inner() ).push(LogRecord {
+				level, message: message.to_string(), fields: fields.to_vec()
+			});
+		}
+	}
+
+	/// 現在設定されている出力先
+	static SINK: RwLock<Option<Box<dyn LogSink>>> = RwLock::new(None);
+
+	/// ログの出力先を `sink` に差し替えます。以降、このクレートが出力する全てのログは `sink` に書き込まれます。
+	pub fn set_sink(sink:impl LogSink+'static) {
+		*SINK.write().unwrap_or_else(|e| e.into_inner() ) = Some(Box::new(sink));
+	}
+
+	/// `set_sink` で設定した出力先を解除し、既定の出力先に戻します。
+	pub fn reset_sink() {
+		*SINK.write().unwrap_or_else(|e| e.into_inner() ) = None;
+	}
+
+	/// 設定されている出力先にログを書き込む
+	pub(super) fn dispatch(level:LogLevel,message:&str,fields:&[(&'static str,String)]) {
+		match SINK.read().unwrap_or_else(|e| e.into_inner() ).as_ref() {
+			Some(sink) => sink.write_record_with_fields(level,message,fields),
+			#[cfg(feature="logging")]
+			None => log::log!(level.to_log_level(),"{}",format_fields(message,fields)),
+			#[cfg(not(feature="logging"))]
+			None => StderrSink.write_record_with_fields(level,message,fields),
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_sink() {
+		let _lock = level::TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner() );
+		let sink = VecSink::new();
+		set_sink(sink.clone());
+		crate::log_at!(Info,"loaded {} items",3);
+		assert_eq!( Err::<i32,i32>(5).unwrap_or_warn_as("fallback"), 5 );
+		set_max_level(LogLevel::Warn);
+		crate::log_at!(Debug,"hidden");
+		emit_log(LogLevel::Error,"failed");
+		set_max_level(LogLevel::Trace);
+		reset_sink();
+		crate::log_at!(Info,"not captured");
+
+		assert_eq!( sink.records(), vec![
+			(LogLevel::Info,"loaded 3 items".to_string()),
+			(LogLevel::Warn,"fallback".to_string()),
+			(LogLevel::Error,"failed".to_string()),
+		] );
+		sink.clear();
+		assert!( sink.records().is_empty() );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_log_kv() {
+		let _lock = level::TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner() );
+		let sink = VecSink::new();
+		set_sink(sink.clone());
+		let (n,p) = (3,"data/input.csv");
+		crate::log_kv!(Info,"loaded",count = n,path = p);
+		crate::log_kv!(Warn,"empty");
+		reset_sink();
+
+		assert_eq!( sink.full_records(), vec![
+			LogRecord { level: LogLevel::Info, message: "loaded".to_string(), fields: vec![("count","3".to_string()),("path","data/input.csv".to_string())] },
+			LogRecord { level: LogLevel::Warn, message: "empty".to_string(), fields: vec![] },
+		] );
+		assert_eq!( format_fields("loaded",&sink.full_records()[0].fields), "loaded count=3 path=data/input.csv" );
+	}
+
+}
+pub use sink::{LogSink,LogRecord,StderrSink,VecSink,set_sink,reset_sink,format_fields};
+
+
+
+/// Result 型や Option 型をアンラップして、エラーをログとして出力する
+mod unwrap_result_option {
+	use super::*;
+
 	pub trait UnwrapOrError<O> {
 		/// アンラップし、失敗したらエラーメッセージを出して終了する
 		fn unwrap_or_error_as(self,message:impl AnyStr) -> O;
@@ -29,10 +330,7 @@ mod unwrap_result_option {
 	impl<O,E> UnwrapOrError<O> for Result<O,E> where E: ToString {
 		fn unwrap_or_error_as(self,message:impl AnyStr) -> O {
 			self.unwrap_or_else(|_| {
-				#[cfg(feature="logging")]
-				log::error!("{}",message);
-				#[cfg(not(feature="logging"))]
-				eprintln!("ERROR: {}",message);
+				emit_log(LogLevel::Error,message.as_ref());
 				exit(1);
 			})
 		}
@@ -40,10 +338,7 @@ mod unwrap_result_option {
 	impl<O> UnwrapOrError<O> for Option<O> {
 		fn unwrap_or_error_as(self,message:impl AnyStr) -> O {
 			self.unwrap_or_else(|| {
-				#[cfg(feature="logging")]
-				log::error!("{}",message);
-				#[cfg(not(feature="logging"))]
-				eprintln!("ERROR: {}",message);
+				emit_log(LogLevel::Error,message.as_ref());
 				exit(1);
 			})
 		}
@@ -52,16 +347,10 @@ mod unwrap_result_option {
 		fn unwrap_or_error_in_detail_as(self,message:impl AnyStr) -> O {
 			self.unwrap_or_else(|e| {
 				let b = Backtrace::force_capture();
-				#[cfg(feature="logging")]
-				log::error!(
+				emit_log(LogLevel::Error,&format!(
 					"{}: {}\nバックトレース:\n{}",
 					message,e.to_string(), b
-				);
-				#[cfg(not(feature="logging"))]
-				eprintln!(
-					"エラー: {} ({})\nバックトレース:\n{}",
-					message,e.to_string(), b
-				);
+				));
 				exit(1);
 			})
 		}
@@ -69,10 +358,7 @@ mod unwrap_result_option {
 	impl<T> UnwrapOrWarnForSameType<T> for Result<T,T> {
 		fn unwrap_or_warn_as(self,message:impl AnyStr) -> T {
 			self.unwrap_or_else(|v| {
-				#[cfg(feature="logging")]
-				log::warn!("{}",message);
-				#[cfg(not(feature="logging"))]
-				eprintln!("WARNING: {}",message);
+				emit_log(LogLevel::Warn,message.as_ref());
 				v
 			})
 		}
@@ -88,22 +374,13 @@ pub use unwrap_result_option::*;
 mod fatal_error {
 	use super::*;
 
-	#[cfg(feature="logging")]
-	extern crate log;
-
 	/// エラーの出力
 	pub fn fatal_error(message:impl AnyStr) -> ! {
 		let b = Backtrace::force_capture();
-		#[cfg(feature="logging")]
-		log::error!(
+		emit_log(LogLevel::Error,&format!(
 			"{}\nバックトレース:{}",
 			message, b
-		);
-		#[cfg(not(feature="logging"))]
-		eprintln!(
-			"{}\nバックトレース:{}",
-			message, b
-		);
+		));
 		exit(1);
 	}
 
@@ -155,3 +432,10 @@ mod collect_result {
 
 }
 pub use collect_result::*;
+
+
+
+/// このモジュールからクレートの `prelude` でアクセスできるようにするアイテムをまとめたもの
+pub(crate) mod for_prelude {
+	pub use super::{LogLevel,LogSink,set_max_level,set_sink};
+}
@@ -29,6 +29,9 @@ crate::iterator::product::for_double_ended_iters_tuple::implement!( I0 T0 0 I1 T
 #[cfg(feature="iterator")]
 crate::iterator::chain::for_iters_tuple::implement!( I0 0 I1 1 I2 2 I3 3 I4 4 I5 5 I6 6 I7 7 I8 8 I9 9 I10 10 I11 11 );
 
+#[cfg(all(feature="iterator",feature="parallel"))]
+crate::iterator::chain::for_parallel_iters::implement!( I0 0 I1 1 I2 2 I3 3 I4 4 I5 5 I6 6 I7 7 I8 8 I9 9 I10 10 I11 11 );
+
 impl_zip_options!( T0 0 T1 1 T2 2 T3 3 T4 4 T5 5 T6 6 T7 7 T8 8 T9 9 T10 10 T11 11 );
 
 #[cfg(feature="iterator")]
@@ -7,9 +7,10 @@ mod types {
 	use super::*;
 
 	use std::{
-		fmt::Display,
+		fmt::{Display,Formatter},
 		convert::AsRef,
-		path::Path
+		path::Path,
+		error::Error
 	};
 
 	compose_struct! {
@@ -19,11 +20,148 @@ mod types {
 		pub trait AnyPath = AsRef<Path> + Display;
 	}
 
+	/// `compose_struct!` の `#[validated(..)]` で生成される `new` に、条件を満たさない値が与えられた場合のエラー
+	#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+	pub struct ValidationError {
+		/// 生成しようとした型の名前
+		pub type_name: &'static str,
+		/// 満たされなかった条件式
+		pub predicate: &'static str
+	}
+
+	impl Display for ValidationError {
+		fn fmt(&self,f:&mut Formatter<'_>) -> std::fmt::Result {
+			write!(f,"{} の値が条件 {} を満たしていません",self.type_name,self.predicate)
+		}
+	}
+
+	impl Error for ValidationError {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_enum_str() {
+		compose_struct! {
+			#[enum_str]
+			#[derive(Debug,Clone,Copy,PartialEq)]
+			enum Direction { North, East, South, West }
+		}
+		assert_eq!( Direction::South.as_str(), "South" );
+		assert_eq!( Direction::ALL, &[Direction::North,Direction::East,Direction::South,Direction::West] );
+		let names = Direction::variants().map(|d| d.as_str() ).collect::<Vec<_>>();
+		assert_eq!( names, ["North","East","South","West"] );
+		// 名前からバリアントに戻す
+		for d in Direction::variants() {
+			assert_eq!( Direction::variants().find(|v| v.as_str()==d.as_str() ), Some(d) );
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_validated() {
+		compose_struct! {
+			#[validated(*value>=0.0 && value.is_finite())]
+			#[derive(Debug,Clone,Copy,PartialEq)]
+			pub struct NonNegative(pub f64);
+		}
+		let x = NonNegative::new(2.5).unwrap();
+		assert_eq!( *x, 2.5 );
+		assert_eq!( x.sqrt(), 2.5_f64.sqrt() );
+		assert_eq!( x.into_inner(), 2.5 );
+		let e = NonNegative::new(-1.0).unwrap_err();
+		assert_eq!( e.type_name, "NonNegative" );
+		assert!( NonNegative::new(f64::INFINITY).is_err() );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_tuple_struct_default() {
+		compose_struct! {
+			#[derive(Debug,PartialEq)]
+			struct Settings(i32 = 3, String = String::from("name"), #[default] Vec<u8>, f64 = 1.5);
+			#[derive(Debug,PartialEq)]
+			struct Pair(#[default] bool, char = 'x');
+		}
+		assert_eq!( Settings::default(), Settings(3,"name".to_string(),vec![],1.5) );
+		assert_eq!( Pair::default(), Pair(false,'x') );
+	}
+
 }
 pub use types::*;
 
 
 
+/// `for_each!` 系のマクロを実際に実行して動作を確認するテスト
+#[cfg(all(test,feature="iterator",feature="parallel"))]
+mod for_each_runtime {
+	use super::*;
+
+	#[test]
+	fn test_custom_reduction() {
+		#[derive(Debug,Default,PartialEq)]
+		struct Stats { count: usize, sum: usize, max: usize }
+		fn merge(a:Stats,b:Stats) -> Stats {
+			Stats { count: a.count+b.count, sum: a.sum+b.sum, max: a.max.max(b.max) }
+		}
+
+		let mut stats = Stats::default();
+		par_for_each! {
+			i = index(1000)
+			fold(combine = merge : stats)
+			{ stats.count += 1; stats.sum += i; stats.max = stats.max.max(i); }
+		}
+		assert_eq!( stats, Stats { count: 1000, sum: 499500, max: 999 } );
+	}
+
+	#[test]
+	fn test_rev() {
+		let mut visited:Vec<usize> = vec![];
+		for_each! {
+			i = index(5)
+			rev()
+			{ visited.push(i); }
+		}
+		assert_eq!( visited, [4,3,2,1,0] );
+
+		let a = [10,20,30];
+		let mut pairs:Vec<(usize,i32)> = vec![];
+		for_each! {
+			i = index(3) x = each(a)
+			rev()
+			{ pairs.push((i,*x)); }
+		}
+		assert_eq!( pairs, [(2,30),(1,20),(0,10)] );
+	}
+
+	#[test]
+	fn test_granularity_and_pool() {
+		let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+		let n = 10000_usize;
+		let mut s = 0_usize;
+		par_for_each! {
+			i = index(n)
+			with_min_len(64)
+			with_max_len(1024)
+			pool(pool)
+			reduce(+(usize):s)
+			{ s = i; }
+		}
+		assert_eq!( s, n*(n-1)/2 );
+
+		// pool の中で実行されていれば、スレッド番号が得られる
+		let mut in_pool = true;
+		par_for_each! {
+			_i = index(100)
+			pool(pool)
+			fold(&&:in_pool)
+			{ in_pool = in_pool && rayon::current_thread_index().is_some() && rayon::current_num_threads()==2; }
+		}
+		assert!( in_pool );
+	}
+
+}
+
+
+
 /// 簡単に所要時間を測定するタイマーモジュール
 #[allow(dead_code)]
 mod instant_timer {
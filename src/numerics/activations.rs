@@ -0,0 +1,78 @@
+//! 機械学習などで用いる活性化関数をまとめたモジュール
+
+use super::*;
+
+
+
+/// シグモイド関数
+mod sigmoid {
+	use super::*;
+
+	/// シグモイド関数 `1/(1+exp(-x))` を計算します。
+	/// * `x<0` では `exp(x)/(1+exp(x))` として計算するので、 `exp` がオーバーフローしません。
+	pub fn sigmoid<F:Float>(x:F) -> F {
+		if x>=F::zero() { (F::one()+(-x).exp()).recip() }
+		else {
+			let e = x.exp();
+			e/(F::one()+e)
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_sigmoid() {
+		assert_eq!( sigmoid(0.0), 0.5 );
+		assert!( (sigmoid(2.0)+sigmoid(-2.0)-1.0_f64).abs()<1e-15 );
+		assert_eq!( sigmoid(1000.0), 1.0 );
+		assert_eq!( sigmoid(-1000.0_f32), 0.0 );
+		assert!( sigmoid(-700.0_f64)>0.0 );
+	}
+
+}
+pub use sigmoid::sigmoid;
+
+
+
+/// ソフトマックス関数
+mod softmax {
+	use super::*;
+
+	/// ソフトマックス関数 `exp(xᵢ)/Σexp(x)` を計算します。
+	/// * 最大値を引いてから指数関数を計算するので、大きな値でもオーバーフローしません。
+	/// * 要素がない場合は空の `Vec` を返します。
+	pub fn softmax<F:Float>(values:impl IntoIterator<Item=F>) -> Vec<F> {
+		let mut v = log_softmax(values);
+		v.iter_mut().for_each(|x| *x = x.exp() );
+		v
+	}
+
+	/// ソフトマックス関数の対数 `xᵢ-log(Σexp(x))` を計算します。
+	/// * `softmax` の結果の対数をとるより精度が良く、非常に小さい確率も `-∞` になりません。
+	pub fn log_softmax<F:Float>(values:impl IntoIterator<Item=F>) -> Vec<F> {
+		let mut v = values.into_iter().collect::<Vec<_>>();
+		// 先に最大値を引いておくことで、 `logsumexp` の結果に大きな値が加わって精度が落ちるのを防ぐ
+		let max = v.iter().copied().fold(F::neg_infinity(),F::max);
+		if max.is_finite() { v.iter_mut().for_each(|x| *x = *x-max ); }
+		let lse = v.iter().copied().logsumexp();
+		v.iter_mut().for_each(|x| *x = *x-lse );
+		v
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_softmax() {
+		let x = [1.0,2.0,3.0,-4.0,0.5];
+		let s = softmax(x);
+		assert!( (s.iter().sum::<f64>()-1.0).abs()<1e-15 );
+		assert!( s[0]<s[1] && s[1]<s[2] && s[3]<s[4] );
+		let l = log_softmax(x);
+		assert!( l.iter().zip(&s).all(|(l,s)| (l.exp()-s).abs()<1e-15 ) );
+
+		let s = softmax([1000.0_f32,1000.0]);
+		assert_eq!( s, vec![0.5,0.5] );
+		assert!( log_softmax([0.0,-2000.0])[1]==-2000.0 );
+		assert_eq!( softmax(Vec::<f64>::new()), Vec::<f64>::new() );
+	}
+
+}
+pub use softmax::{softmax,log_softmax};
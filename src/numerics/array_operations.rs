@@ -0,0 +1,139 @@
+//! `ndarray` の多次元配列に対する数値演算をまとめたモジュール
+
+use ndarray::{ArrayBase,Array,Data,Dimension,Axis};
+use std::ops::{Add,Mul};
+
+
+
+/// 軸に沿った累積和/累積積
+mod cumulative {
+	use super::*;
+
+	/// 軸 `axis` に沿って各要素を `f` で累積した、同じ形状の配列を生成する
+	fn accumulate<A,S,D>(a:&ArrayBase<S,D>,axis:Axis,f:impl Fn(A,A)->A) -> Array<A,D>
+	where A: Clone, S: Data<Elem=A>, D: Dimension
+	{
+		let mut r = a.to_owned();
+		r.accumulate_axis_inplace(axis,|p,c| *c = f(p.clone(),c.clone()) );
+		r
+	}
+
+	/// 配列 `a` の軸 `axis` に沿った累積和を計算し、同じ形状の配列として返します。
+	/// * `axis` が配列の次元数以上の場合はパニックします。
+	pub fn cumsum<A,S,D>(a:&ArrayBase<S,D>,axis:Axis) -> Array<A,D>
+	where A: Clone+Add<Output=A>, S: Data<Elem=A>, D: Dimension
+	{
+		accumulate(a,axis,|p,c| p+c )
+	}
+
+	/// 配列 `a` の軸 `axis` に沿った累積積を計算し、同じ形状の配列として返します。
+	/// * `axis` が配列の次元数以上の場合はパニックします。
+	pub fn cumprod<A,S,D>(a:&ArrayBase<S,D>,axis:Axis) -> Array<A,D>
+	where A: Clone+Mul<Output=A>, S: Data<Elem=A>, D: Dimension
+	{
+		accumulate(a,axis,|p,c| p*c )
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_cumulative() {
+		use ndarray::array;
+
+		let a = array![1,2,3,4];
+		assert_eq!( cumsum(&a,Axis(0)), array![1,3,6,10] );
+		assert_eq!( cumprod(&a,Axis(0)), array![1,2,6,24] );
+
+		let b = array![[1,2,3],[4,5,6]];
+		assert_eq!( cumsum(&b,Axis(0)), array![[1,2,3],[5,7,9]] );
+		assert_eq!( cumsum(&b,Axis(1)), array![[1,3,6],[4,9,15]] );
+		assert_eq!( cumprod(&b,Axis(0)), array![[1,2,3],[4,10,18]] );
+		assert_eq!( cumprod(&b,Axis(1)), array![[1,2,6],[4,20,120]] );
+	}
+
+}
+pub use cumulative::{cumsum,cumprod};
+
+
+
+/// 同じ形状の2つの配列を重み付きで混合する
+#[cfg(feature="numerics")]
+mod blend {
+	use super::*;
+	use ndarray::Zip;
+	use num::Float;
+
+	/// `blend` の重み `t` として受け入れ可能な型 (スカラー値または同じ形状の配列) を定義するトレイト
+	pub trait BlendWeight<A,D> {
+		/// 2つの配列 `a`, `b` を重み `self` で混合する。 `clamp` が真なら重みを `[0,1]` に制限する。
+		fn blend_impl<S1,S2>(self,a:&ArrayBase<S1,D>,b:&ArrayBase<S2,D>,clamp:bool) -> Array<A,D>
+		where S1: Data<Elem=A>, S2: Data<Elem=A>;
+	}
+
+	/// 重み `t` による混合 `x*(1-t)+y*t` を計算する
+	#[inline]
+	fn mix<A: Float>(x:A,y:A,t:A,clamp:bool) -> A {
+		let t = if clamp { t.max(A::zero()).min(A::one()) } else { t };
+		x*(A::one()-t)+y*t
+	}
+
+	macro_rules! impl_scalar_weight {
+		( $($t:ty)+ ) => { $(
+			impl<D: Dimension> BlendWeight<$t,D> for $t {
+				fn blend_impl<S1,S2>(self,a:&ArrayBase<S1,D>,b:&ArrayBase<S2,D>,clamp:bool) -> Array<$t,D>
+				where S1: Data<Elem=$t>, S2: Data<Elem=$t> {
+					Zip::from(a).and(b)
+					.map_collect(|&x,&y| mix(x,y,self,clamp) )
+				}
+			}
+		)+ };
+	}
+	impl_scalar_weight!( f64 f32 );
+
+	impl<A,S,D> BlendWeight<A,D> for &ArrayBase<S,D>
+	where A: Float, S: Data<Elem=A>, D: Dimension
+	{
+		fn blend_impl<S1,S2>(self,a:&ArrayBase<S1,D>,b:&ArrayBase<S2,D>,clamp:bool) -> Array<A,D>
+		where S1: Data<Elem=A>, S2: Data<Elem=A> {
+			Zip::from(a).and(b).and(self)
+			.map_collect(|&x,&y,&t| mix(x,y,t,clamp) )
+		}
+	}
+
+	/// 同じ形状の配列 `a`, `b` を要素ごとに `a*(1-t)+b*t` で混合します。 `lerp` の配列版です。
+	/// * `t` にはスカラー値か、 `a`, `b` と同じ形状の配列の参照 (要素ごとの重み) を指定します。
+	/// * `t` は `[0,1]` に制限されないので、範囲外の値を与えると外挿になります。
+	/// * 配列の形状が一致しない場合はパニックします。
+	pub fn blend<A,S1,S2,D,W>(a:&ArrayBase<S1,D>,b:&ArrayBase<S2,D>,t:W) -> Array<A,D>
+	where S1: Data<Elem=A>, S2: Data<Elem=A>, D: Dimension, W: BlendWeight<A,D>
+	{
+		t.blend_impl(a,b,false)
+	}
+
+	/// `blend` と同様ですが、重み `t` を `[0,1]` に制限してから混合します。
+	pub fn blend_clamped<A,S1,S2,D,W>(a:&ArrayBase<S1,D>,b:&ArrayBase<S2,D>,t:W) -> Array<A,D>
+	where S1: Data<Elem=A>, S2: Data<Elem=A>, D: Dimension, W: BlendWeight<A,D>
+	{
+		t.blend_impl(a,b,true)
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_blend() {
+		use ndarray::array;
+
+		let a = array![[0.0,2.0],[4.0,-2.0]];
+		let b = array![[2.0,4.0],[0.0,2.0]];
+		assert_eq!( blend(&a,&b,0.0), a );
+		assert_eq!( blend(&a,&b,0.5), array![[1.0,3.0],[2.0,0.0]] );
+		assert_eq!( blend(&a,&b,1.0), b );
+		assert_eq!( blend(&a,&b,2.0), array![[4.0,6.0],[-4.0,6.0]] );
+		assert_eq!( blend_clamped(&a,&b,2.0), b );
+
+		let t = array![[0.0,0.5],[1.0,1.5]];
+		assert_eq!( blend(&a,&b,&t), array![[0.0,3.0],[0.0,4.0]] );
+		assert_eq!( blend_clamped(&a,&b,&t), array![[0.0,3.0],[0.0,2.0]] );
+	}
+
+}
+#[cfg(feature="numerics")]
+pub use blend::{blend,blend_clamped};
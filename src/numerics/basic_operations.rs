@@ -23,11 +23,19 @@ mod operate_and_assign {
 	// 以下では最大/最小の複合代入演算子を定義しているが、 `Ord` と `Float` であえて別のトレイトにしている。そうしないとコンフリクトが発生するから。
 
 	/// 最大/最小にも複合代入演算子を用意する
-	pub trait MinMaxAssignForOrd {
+	pub trait MinMaxAssignForOrd: Sized {
 		/// もう一方の値と比較し、小さい方を代入する
 		fn min_assign(&mut self,rhs:Self);
 		/// もう一方の値と比較し、大きい方を代入する
 		fn max_assign(&mut self,rhs:Self);
+		/// イテレータの全ての値と比較し、最も小さい値を代入する。イテレータが空の場合は変化しない。
+		fn min_assign_all(&mut self,iter:impl IntoIterator<Item=Self>) {
+			for v in iter { self.min_assign(v); }
+		}
+		/// イテレータの全ての値と比較し、最も大きい値を代入する。イテレータが空の場合は変化しない。
+		fn max_assign_all(&mut self,iter:impl IntoIterator<Item=Self>) {
+			for v in iter { self.max_assign(v); }
+		}
 	}
 	impl<T:Ord+Copy> MinMaxAssignForOrd for T {
 		fn min_assign(&mut self,rhs:Self) {
@@ -39,11 +47,19 @@ mod operate_and_assign {
 	}
 
 	/// 最大/最小にも複合代入演算子を用意する
-	pub trait MinMaxAssignForFloat {
+	pub trait MinMaxAssignForFloat: Sized {
 		/// もう一方の値と比較し、小さい方を代入する
 		fn min_assign(&mut self,rhs:Self);
 		/// もう一方の値と比較し、大きい方を代入する
 		fn max_assign(&mut self,rhs:Self);
+		/// イテレータの全ての値と比較し、最も小さい値を代入する。イテレータが空の場合は変化しない。
+		fn min_assign_all(&mut self,iter:impl IntoIterator<Item=Self>) {
+			for v in iter { self.min_assign(v); }
+		}
+		/// イテレータの全ての値と比較し、最も大きい値を代入する。イテレータが空の場合は変化しない。
+		fn max_assign_all(&mut self,iter:impl IntoIterator<Item=Self>) {
+			for v in iter { self.max_assign(v); }
+		}
 	}
 	#[cfg(feature="numerics")]
 	impl<T:Float> MinMaxAssignForFloat for T {
@@ -55,6 +71,110 @@ mod operate_and_assign {
 		}
 	}
 
+	/// 整数の飽和演算の複合代入版
+	pub trait SaturatingAssign {
+		/// 飽和加算 `saturating_add` の結果を代入する
+		fn saturating_add_assign(&mut self,rhs:Self);
+		/// 飽和減算 `saturating_sub` の結果を代入する
+		fn saturating_sub_assign(&mut self,rhs:Self);
+		/// 飽和乗算 `saturating_mul` の結果を代入する
+		fn saturating_mul_assign(&mut self,rhs:Self);
+	}
+
+	/// 整数のラップアラウンド演算の複合代入版
+	pub trait WrappingAssign {
+		/// `wrapping_add` の結果を代入する
+		fn wrapping_add_assign(&mut self,rhs:Self);
+		/// `wrapping_sub` の結果を代入する
+		fn wrapping_sub_assign(&mut self,rhs:Self);
+		/// `wrapping_mul` の結果を代入する
+		fn wrapping_mul_assign(&mut self,rhs:Self);
+	}
+
+	/// 整数のオーバーフローを検査する演算の複合代入版
+	pub trait CheckedAssign {
+		/// `checked_add` が成功すれば結果を代入して `true` を返し、オーバーフローした場合は値を変えずに `false` を返す
+		fn checked_add_assign(&mut self,rhs:Self) -> bool;
+		/// `checked_sub` が成功すれば結果を代入して `true` を返し、オーバーフローした場合は値を変えずに `false` を返す
+		fn checked_sub_assign(&mut self,rhs:Self) -> bool;
+		/// `checked_mul` が成功すれば結果を代入して `true` を返し、オーバーフローした場合は値を変えずに `false` を返す
+		fn checked_mul_assign(&mut self,rhs:Self) -> bool;
+	}
+
+	macro_rules! impl_integer_assign {
+		( $($t:ty)+ ) => { $(
+			impl SaturatingAssign for $t {
+				fn saturating_add_assign(&mut self,rhs:Self) { *self = self.saturating_add(rhs); }
+				fn saturating_sub_assign(&mut self,rhs:Self) { *self = self.saturating_sub(rhs); }
+				fn saturating_mul_assign(&mut self,rhs:Self) { *self = self.saturating_mul(rhs); }
+			}
+			impl WrappingAssign for $t {
+				fn wrapping_add_assign(&mut self,rhs:Self) { *self = self.wrapping_add(rhs); }
+				fn wrapping_sub_assign(&mut self,rhs:Self) { *self = self.wrapping_sub(rhs); }
+				fn wrapping_mul_assign(&mut self,rhs:Self) { *self = self.wrapping_mul(rhs); }
+			}
+			impl CheckedAssign for $t {
+				fn checked_add_assign(&mut self,rhs:Self) -> bool {
+					self.checked_add(rhs).map(|v| *self = v ).is_some()
+				}
+				fn checked_sub_assign(&mut self,rhs:Self) -> bool {
+					self.checked_sub(rhs).map(|v| *self = v ).is_some()
+				}
+				fn checked_mul_assign(&mut self,rhs:Self) -> bool {
+					self.checked_mul(rhs).map(|v| *self = v ).is_some()
+				}
+			}
+		)+ };
+	}
+	impl_integer_assign!( i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize );
+
+	#[cfg(test)]
+	#[test]
+	fn test_integer_assign() {
+		let mut h = [250_u8,3];
+		h[0].saturating_add_assign(10);
+		h[1].saturating_sub_assign(5);
+		assert_eq!( h, [255,0] );
+		let mut v = -100_i8;
+		v.saturating_mul_assign(2);
+		assert_eq!( v, i8::MIN );
+
+		let mut w = 250_u8;
+		w.wrapping_add_assign(10);
+		assert_eq!( w, 4 );
+		w.wrapping_sub_assign(5);
+		assert_eq!( w, 255 );
+		w.wrapping_mul_assign(2);
+		assert_eq!( w, 254 );
+
+		let mut c = 100_i32;
+		assert!( c.checked_mul_assign(3) );
+		assert_eq!( c, 300 );
+		assert!( !c.checked_mul_assign(i32::MAX) );
+		assert_eq!( c, 300 );
+		assert!( !0_u64.checked_sub_assign(1) );
+		assert!( c.checked_add_assign(-300) );
+		assert_eq!( c, 0 );
+	}
+
+	#[cfg(all(test,feature="numerics"))]
+	#[test]
+	fn test_min_max_assign_all() {
+		let mut m = 5;
+		MinMaxAssignForOrd::min_assign_all(&mut m,[7,3,9]);
+		assert_eq!( m, 3 );
+		MinMaxAssignForOrd::max_assign_all(&mut m,[7,3,9]);
+		assert_eq!( m, 9 );
+		MinMaxAssignForOrd::max_assign_all(&mut m,[]);
+		assert_eq!( m, 9 );
+
+		let mut x = 0.5_f64;
+		MinMaxAssignForFloat::min_assign_all(&mut x,vec![1.0,f64::NAN,-2.5]);
+		assert_eq!( x, -2.5 );
+		MinMaxAssignForFloat::max_assign_all(&mut x,(0..4).map(|i| i as f64 ));
+		assert_eq!( x, 3.0 );
+	}
+
 }
 pub use operate_and_assign::*;
 
@@ -128,6 +248,8 @@ pub use min_max::*;
 /// `Float` に従う型の最大/最小を多数の要素でも使えるようにする。 NaN の伝播の仕方に合わせて複数のメソッドを用意する。
 mod float_min_max {
 	use super::*;
+	#[cfg(feature="parallel")]
+	use rayon::iter::ParallelIterator;
 
 	compose_struct! {
 		trait Iter<T> = IntoIterator<Item=T> where T: ?Sized;
@@ -189,6 +311,72 @@ mod float_min_max {
 		m
 	}
 
+	#[cfg(feature="parallel")]
+	/// `FloatMinMax` の並列イテレータ版
+	pub trait ParallelFloatMinMax<T> {
+		/// 複数の浮動小数の中から最小の値を並列に計算します。値に NaN が含まれていれば無視されます。全ての値が NaN の場合や値が含まれていない場合は NaN を返します。
+		fn minimum(self) -> T;
+		/// 複数の浮動小数の中から最大の値を並列に計算します。値に NaN が含まれていれば無視されます。全ての値が NaN の場合や値が含まれていない場合は NaN を返します。
+		fn maximum(self) -> T;
+		/// 複数の浮動小数の中から最小の値を並列に計算します。値のうちどれか1つでも NaN がある場合や値が含まれていない場合 NaN を返します。
+		fn minimum_propagate(self) -> T;
+		/// 複数の浮動小数の中から最大の値を並列に計算します。値のうちどれか1つでも NaN がある場合や値が含まれていない場合 NaN を返します。
+		fn maximum_propagate(self) -> T;
+	}
+
+	#[cfg(feature="parallel")]
+	impl<T,I> ParallelFloatMinMax<T> for I where I: ParallelIterator<Item=T>, T: Float+Send {
+		fn minimum(self) -> T {
+			self.reduce(T::nan,ignore_nan(min))
+		}
+		fn maximum(self) -> T {
+			self.reduce(T::nan,ignore_nan(max))
+		}
+		fn minimum_propagate(self) -> T {
+			self.reduce_with(propagate_nan(min)).unwrap_or(T::nan())
+		}
+		fn maximum_propagate(self) -> T {
+			self.reduce_with(propagate_nan(max)).unwrap_or(T::nan())
+		}
+	}
+
+	/// NaN を無視する演算に変換する。単位元の NaN と合わせた場合は他方の値を返す。
+	#[cfg(feature="parallel")]
+	fn ignore_nan<T:Float>(f:impl ReduceFn<T>) -> impl ReduceFn<T> {
+		move |a,v| match (a.is_nan(),v.is_nan()) {
+			(true,_) => v,
+			(false,true) => a,
+			(false,false) => f(a,v)
+		}
+	}
+
+	/// NaN を伝播する演算に変換する
+	#[cfg(feature="parallel")]
+	fn propagate_nan<T:Float>(f:impl ReduceFn<T>) -> impl ReduceFn<T> {
+		move |a,v| if a.is_nan() || v.is_nan() { T::nan() } else { f(a,v) }
+	}
+
+	#[cfg(all(test,feature="parallel"))]
+	#[test]
+	fn test_parallel_float_min_max() {
+		use rayon::iter::IntoParallelIterator;
+		let data = (0..1000).map(|i| ((i*7919)%1009) as f64 - 500.0 ).collect::<Vec<_>>();
+		let mut with_nan = data.clone();
+		with_nan[321] = f64::NAN;
+
+		for v in [&data,&with_nan] {
+			assert_eq!( ParallelFloatMinMax::minimum(v.clone().into_par_iter()), FloatMinMax::minimum(v.clone()) );
+			assert_eq!( ParallelFloatMinMax::maximum(v.clone().into_par_iter()), FloatMinMax::maximum(v.clone()) );
+		}
+		assert_eq!( ParallelFloatMinMax::minimum_propagate(data.clone().into_par_iter()), FloatMinMax::minimum_propagate(data.clone()) );
+		assert_eq!( ParallelFloatMinMax::maximum_propagate(data.clone().into_par_iter()), FloatMinMax::maximum_propagate(data.clone()) );
+		assert!( ParallelFloatMinMax::minimum_propagate(with_nan.clone().into_par_iter()).is_nan() );
+		assert!( ParallelFloatMinMax::maximum_propagate(with_nan.into_par_iter()).is_nan() );
+		assert!( ParallelFloatMinMax::minimum(vec![f64::NAN;100].into_par_iter()).is_nan() );
+		assert!( ParallelFloatMinMax::maximum(Vec::<f32>::new().into_par_iter()).is_nan() );
+		assert!( ParallelFloatMinMax::minimum_propagate(Vec::<f32>::new().into_par_iter()).is_nan() );
+	}
+
 }
 #[cfg(feature="numerics")]
 pub use float_min_max::*;
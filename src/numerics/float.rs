@@ -100,14 +100,17 @@ mod rounding {
 		}
 	}
 
-	impl<T> Rounding<T> where T: Float, f32: Into<T> {
+	impl<T> Rounding<T> where T: Float {
 		pub fn doit(&self) -> T {
 
 			if self.value.is_nan() { return self.value; }
 
+			// `f32: Into<T>` を要求せずに定数を変換する (`half::f16` など `num::Float` を実装する任意の型に対応するため)
+			let lit = |v:f32| -> T { <T as NumCast>::from(v).unwrap() };
+
 			let mut x = self.value;
 
-			if self.digit!=0 { x = x * 10.0.into().powi(self.digit); }
+			if self.digit!=0 { x = x * lit(10.0).powi(self.digit); }
 
 			let is_positive = x.is_sign_positive();
 
@@ -169,8 +172,8 @@ mod rounding {
 				};
 
 				// 剰余の値にマッチ
-				(@rem 1) => { 10.0.into() };
-				(@rem 2) => { 20.0.into() };
+				(@rem 1) => { lit(1.0) };
+				(@rem 2) => { lit(2.0) };
 
 				// 以下は1つの丸め方に対して条件分岐がある場合を処理している
 				// x: パース済 y: パース中 z: 未パース
@@ -183,7 +186,7 @@ mod rounding {
 				) => { r!(@m
 					x(
 						rem($rem) input($($input)+) $($x)*
-						pattern(($sp,r)) condition(r $($op)+ $t.into()) func($func)
+						pattern(($sp,r)) condition(r $($op)+ lit($t)) func($func)
 					)
 					y() $($z)+
 				) };
@@ -316,13 +319,189 @@ mod rounding {
 				}
 			} };
 
-			if self.digit!=0 { x = x / 10.0.into().powi(self.digit); }
+			if self.digit!=0 { x = x / lit(10.0).powi(self.digit); }
 
 			x
 
 		}
 	}
 
+	/// 実数と複素数に丸めのメソッドを提供するトレイト
+	/// * 複素数では実部と虚部をそれぞれ独立に丸めます。
+	pub trait RoundingMethods: Sized {
+		/// 値を丸め方 `strategy` により整数に丸めます。
+		fn rounding(&self,strategy:Strategy) -> Self {
+			self.rounding_with_precision(strategy,0)
+		}
+		/// 値を丸め方 `strategy` により10進数で小数点以下 `digit` 桁に丸めます。
+		fn rounding_with_precision(&self,strategy:Strategy,digit:i32) -> Self;
+		/// 値を丸め方 `strategy` により有効数字 `sig_figs` 桁に丸めます。
+		/// * `log10(|x|)` から最上位の桁を求め、 `rounding_with_precision` で丸めます。
+		/// * `0` や非正規化数、無限大、 NaN の場合と、 `sig_figs` が `0` の場合は値をそのまま返します。
+		fn rounding_significant(&self,strategy:Strategy,sig_figs:u32) -> Self;
+	}
+	macro_rules! impl_rounding_methods {
+		( $($t:ty)+ ) => { $(
+			impl RoundingMethods for $t {
+				fn rounding_with_precision(&self,strategy:Strategy,digit:i32) -> Self {
+					Rounding { value: *self, strategy, digit }.doit()
+				}
+				fn rounding_significant(&self,strategy:Strategy,sig_figs:u32) -> Self {
+					if !self.is_normal() || sig_figs==0 { return *self; }
+					let exponent = Float::log10(self.abs()).floor().to_i32().unwrap();
+					self.rounding_with_precision(strategy,sig_figs as i32-1-exponent)
+				}
+			}
+			impl RoundingMethods for Complex<$t> {
+				fn rounding_with_precision(&self,strategy:Strategy,digit:i32) -> Self {
+					Complex {
+						re: self.re.rounding_with_precision(strategy,digit),
+						im: self.im.rounding_with_precision(strategy,digit)
+					}
+				}
+				fn rounding_significant(&self,strategy:Strategy,sig_figs:u32) -> Self {
+					Complex {
+						re: self.re.rounding_significant(strategy,sig_figs),
+						im: self.im.rounding_significant(strategy,sig_figs)
+					}
+				}
+			}
+		)+ };
+	}
+	impl_rounding_methods!( f64 f32 );
+	#[cfg(feature="half")]
+	impl_rounding_methods!( half::f16 );
+
+	#[cfg(test)]
+	#[test]
+	fn test_rounding_complex() {
+		use Strategy::*;
+		assert_eq!( Complex{re:1.4,im:-1.6}.rounding(ToNearestOrTowardInfinity), Complex{re:1.0,im:-2.0} );
+		assert_eq!( Complex{re:2.5_f32,im:-2.5}.rounding(ToNearestOrEven), Complex{re:2.0,im:-2.0} );
+		assert_eq!( Complex{re:1.4,im:-1.6}.rounding(Down), Complex{re:1.0,im:-2.0} );
+		assert_eq!( Complex{re:0.125,im:-0.375}.rounding_with_precision(ToNearestOrEven,2), Complex{re:0.12,im:-0.38} );
+		assert_eq!( 1.25.rounding_with_precision(Up,1), 1.3 );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_rounding_significant() {
+		use Strategy::*;
+		assert_eq!( 0.0001234.rounding_significant(ToNearestOrTowardInfinity,2), 0.00012 );
+		assert_eq!( 123456.0.rounding_significant(ToNearestOrTowardInfinity,3), 123000.0 );
+		assert_eq!( (-98.76).rounding_significant(TowardZero,2), -98.0 );
+		assert_eq!( 9.96.rounding_significant(ToNearestOrTowardInfinity,2), 10.0 );
+		assert_eq!( 2.5_f32.rounding_significant(ToNearestOrEven,1), 2.0 );
+		assert_eq!( 0.0.rounding_significant(Up,3), 0.0 );
+		assert_eq!( (f64::MIN_POSITIVE/4.0).rounding_significant(Up,1), f64::MIN_POSITIVE/4.0 );
+		assert!( f64::NAN.rounding_significant(Up,3).is_nan() );
+		assert_eq!( Complex{re:1234.0,im:-0.05678}.rounding_significant(ToNearestOrTowardInfinity,2), Complex{re:1200.0,im:-0.057} );
+	}
+
+	/// 浮動小数を丸めて整数型に変換するトレイト
+	/// * `x.round() as i64` のような変換とは異なり、表現できない値は飽和させずに `None` を返します。
+	pub trait RoundToInteger {
+		/// 値を丸め方 `strategy` により整数に丸め、 `i64` に変換します。 NaN や無限大、範囲外の値の場合は `None` を返します。
+		fn rounding_to_i64(&self,strategy:Strategy) -> Option<i64>;
+		/// 値を丸め方 `strategy` により整数に丸め、 `u64` に変換します。 NaN や無限大、範囲外の値の場合は `None` を返します。
+		fn rounding_to_u64(&self,strategy:Strategy) -> Option<u64>;
+	}
+	impl<T: Float> RoundToInteger for T {
+		fn rounding_to_i64(&self,strategy:Strategy) -> Option<i64> {
+			Rounding { value: *self, strategy, digit: 0 }.doit().to_i64()
+		}
+		fn rounding_to_u64(&self,strategy:Strategy) -> Option<u64> {
+			Rounding { value: *self, strategy, digit: 0 }.doit().to_u64()
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_rounding_to_integer() {
+		use Strategy::*;
+		assert_eq!( 2.5.rounding_to_i64(ToNearestOrEven), Some(2) );
+		assert_eq!( (-2.5).rounding_to_i64(ToNearestOrTowardInfinity), Some(-3) );
+		assert_eq!( 1.2_f32.rounding_to_u64(Up), Some(2) );
+		// 2^63 は i64 の範囲外、それより1つ小さい f64 は範囲内
+		let limit = 9_223_372_036_854_775_808.0_f64;
+		assert_eq!( limit.rounding_to_i64(Down), None );
+		assert_eq!( limit.rounding_to_u64(Down), Some(1<<63) );
+		assert_eq!( (limit-1024.0).rounding_to_i64(Down), Some(i64::MAX-1023) );
+		assert_eq!( (-limit).rounding_to_i64(Down), Some(i64::MIN) );
+		assert_eq!( (-limit-2048.0).rounding_to_i64(Up), None );
+		assert_eq!( 18_446_744_073_709_551_616.0_f64.rounding_to_u64(Down), None );
+		// 負の値は丸めた結果が -0 になる場合のみ u64 に変換できる
+		assert_eq!( (-0.4).rounding_to_u64(ToNearestOrEven), Some(0) );
+		assert_eq!( (-0.6).rounding_to_u64(ToNearestOrEven), None );
+		assert_eq!( f64::NAN.rounding_to_i64(Down), None );
+		assert_eq!( f64::INFINITY.rounding_to_u64(Down), None );
+		assert_eq!( f32::NEG_INFINITY.rounding_to_i64(Down), None );
+	}
+
+	/// 浮動小数を任意の刻み幅の倍数に丸めるトレイト
+	pub trait RoundToMultiple: Sized {
+		/// 値を丸め方 `strategy` により `step` の倍数に丸めます。グリッドへのスナップなどに使えます。
+		/// * `(self/step)` を整数に丸めてから `step` を掛けます。
+		/// * 丸め方の向きは `step` の符号によらず、整数に丸める場合と同じ意味になります (`step` は絶対値として扱います)。
+		/// * `step` が `0` の場合は値をそのまま返します。
+		fn rounding_to_multiple(&self,strategy:Strategy,step:Self) -> Self;
+	}
+	impl<T: Float> RoundToMultiple for T {
+		fn rounding_to_multiple(&self,strategy:Strategy,step:Self) -> Self {
+			if step.is_zero() { return *self; }
+			let step = step.abs();
+			Rounding { value: *self/step, strategy, digit: 0 }.doit() * step
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_rounding_to_multiple() {
+		use Strategy::*;
+		assert_eq!( 1.3.rounding_to_multiple(Down,0.25), 1.25 );
+		assert_eq!( 1.3.rounding_to_multiple(Up,0.25), 1.5 );
+		assert_eq!( 1.3.rounding_to_multiple(ToNearestOrTowardInfinity,0.25), 1.25 );
+		assert_eq!( 1.375.rounding_to_multiple(ToNearestOrEven,0.25), 1.5 );
+		assert_eq!( 1.375.rounding_to_multiple(ToNearestOrOdd,0.25), 1.25 );
+		assert_eq!( (-1.3).rounding_to_multiple(Down,0.25), -1.5 );
+		assert_eq!( (-1.3).rounding_to_multiple(TowardZero,0.25), -1.25 );
+		assert_eq!( (-1.3_f32).rounding_to_multiple(TowardInfinity,0.25), -1.5 );
+
+		assert_eq!( 12.5.rounding_to_multiple(ToNearestOrEven,5.0), 10.0 );
+		assert_eq!( 12.5.rounding_to_multiple(ToNearestOrTowardInfinity,5.0), 15.0 );
+		assert_eq!( (-12.5).rounding_to_multiple(ToNearestOrTowardInfinity,5.0), -15.0 );
+		assert_eq!( (-12.5).rounding_to_multiple(ToNearestOrUp,5.0), -10.0 );
+		assert_eq!( 13.0.rounding_to_multiple(Down,-5.0), 10.0 );
+		assert_eq!( 13.0.rounding_to_multiple(Up,0.0), 13.0 );
+	}
+
+	/// 値 `x` を丸め方 `strategy` により10進数で小数点以下 `digits` 桁に丸め、その桁数で文字列に整形します。
+	/// * `{:.2}` などの書式指定とは異なり、丸め方を指定できます。
+	/// * 丸めた値を指定の桁数で整形するので、 `0.30000000000000004` のような浮動小数の誤差は表示されません。
+	/// * `digits` が負の場合は整数部の位で丸め、小数部なしで整形します。
+	/// * 丸めた結果が `-0` になる場合は `0` として整形します。
+	pub fn format_rounded<T>(x:T,strategy:Strategy,digits:i32) -> String
+	where T: Float + std::fmt::Display
+	{
+		let mut r = Rounding { value: x, strategy, digit: digits }.doit();
+		if r.is_zero() { r = T::zero(); }
+		format!("{:.*}",digits.max(0) as usize,r)
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_format_rounded() {
+		use Strategy::{ToNearestOrEven as E,ToNearestOrTowardInfinity as I};
+		assert_eq!( (format_rounded(0.125,E,2),format_rounded(0.125,I,2)), ("0.12".to_string(),"0.13".to_string()) );
+		assert_eq!( (format_rounded(-0.125,E,2),format_rounded(-0.125,I,2)), ("-0.12".to_string(),"-0.13".to_string()) );
+		assert_eq!( (format_rounded(0.375,E,2),format_rounded(0.375,I,2)), ("0.38".to_string(),"0.38".to_string()) );
+		assert_eq!( (format_rounded(2.5,E,0),format_rounded(2.5,I,0)), ("2".to_string(),"3".to_string()) );
+		assert_eq!( (format_rounded(125.0,E,-1),format_rounded(125.0,I,-1)), ("120".to_string(),"130".to_string()) );
+		assert_eq!( format_rounded(0.1+0.2,E,3), "0.300" );
+		assert_eq!( format_rounded(-0.001,I,2), "0.00" );
+		assert_eq!( format_rounded(1.5f32,E,1), "1.5" );
+	}
+
 	#[cfg(test)]
 	#[test]
 	/// 丸める処理が適切に動作するかテストする
@@ -388,8 +567,185 @@ mod rounding {
 
 	}
 
+	#[cfg(test)]
+	#[test]
+	/// `num::Float` のみを境界とするジェネリックな文脈から丸められるかテストする
+	fn test_rounding_generic_float() {
+		use Strategy::*;
+
+		fn r<T: Float>(value:T,strategy:Strategy,digit:i32) -> T {
+			Rounding { value, strategy, digit }.doit()
+		}
+
+		assert_eq!( r(-1.5_f32,ToNearestOrEven,0), -2.0 );
+		assert_eq!( r(0.5_f32,ToNearestOrOdd,0), 1.0 );
+		assert_eq!( r(1.5_f32,ToNearestOrTowardZero,0), 1.0 );
+		assert!( r(-0.2_f32,Up,0).is_sign_negative() );
+		assert_eq!( r(1.25_f32,Up,1), 1.3 );
+		assert_eq!( r(2.5_f64,ToNearestOrDown,0), 2.0 );
+		assert!( r(f32::NAN,Down,0).is_nan() );
+	}
+
+	#[cfg(all(test,feature="half"))]
+	#[test]
+	/// `test_rounding` の一部の行を `half::f16` で確認する
+	fn test_rounding_f16() {
+		use Strategy::*;
+		use half::f16;
+
+		let h = f16::from_f64;
+		// f16 の精度 (有効桁数約3桁) に合わせた許容誤差で比較する
+		let close = |a:f16,b:f64| (a.to_f64()-b).abs() <= f16::EPSILON.to_f64()*b.abs().max(1.0) ;
+
+		let input = [-1.8,-1.5,-0.5,0.2,0.5,1.2,1.5,1.8];
+		let expected = [
+			(Down,                      [-2.0,-2.0,-1.0,0.0,0.0,1.0,1.0,1.0]),
+			(Up,                        [-1.0,-1.0,-0.0,1.0,1.0,2.0,2.0,2.0]),
+			(TowardZero,                [-1.0,-1.0,-0.0,0.0,0.0,1.0,1.0,1.0]),
+			(ToNearestOrTowardInfinity, [-2.0,-2.0,-1.0,0.0,1.0,1.0,2.0,2.0]),
+			(ToNearestOrEven,           [-2.0,-2.0,-0.0,0.0,0.0,1.0,2.0,2.0]),
+			(ToNearestOrOdd,            [-2.0,-1.0,-1.0,0.0,1.0,1.0,1.0,2.0]),
+		];
+		for (s,exp) in expected {
+			for (&i,e) in std::iter::zip(input.iter(),exp) {
+				let c = h(i).rounding(s);
+				assert!( close(c,e), "f16({:+}).rounding(Strategy::{:?}) = {} != {:+}", i, s, c, e );
+			}
+		}
+
+		assert!( close(h(1.25).rounding_with_precision(Up,1),1.3) );
+		assert!( close(h(123.4).rounding_significant(ToNearestOrTowardInfinity,2),120.0) );
+		assert_eq!( h(2.5).rounding_to_i64(ToNearestOrEven), Some(2) );
+		assert!( f16::NAN.rounding(Down).is_nan() );
+	}
+
 }
 pub use rounding::{
 	Rounding as FloatRounding,
-	Strategy as FloatRoundingStrategy
+	Strategy as FloatRoundingStrategy,
+	RoundingMethods as FloatRoundingMethods,
+	RoundToMultiple as FloatRoundToMultiple,
+	RoundToInteger as FloatRoundToInteger,
+	format_rounded
 };
+
+
+
+/// 浮動小数を ULP (最終桁の単位) により比較するモジュール
+mod ulps {
+
+	/// 浮動小数を ULP (unit in the last place) の距離で比較するトレイト
+	pub trait FloatUlps: Sized {
+		/// 2つの浮動小数の間に表現可能な浮動小数がいくつあるか (ULP 単位の距離) を計算します。
+		/// * どちらかが `NaN` の場合は `None` を返します。
+		/// * `+0.0` と `-0.0` の距離は0となります。
+		fn ulps_distance(self,other:Self) -> Option<u64>;
+		/// 2つの浮動小数の距離が `max_ulps` ULP 以内であるか判定します。どちらかが `NaN` の場合は `false` を返します。
+		fn eq_ulps(self,other:Self,max_ulps:u64) -> bool {
+			self.ulps_distance(other).is_some_and(|d| d<=max_ulps )
+		}
+		/// `toward` の方向に隣接する表現可能な浮動小数を返します (C の `nextafter` と同じ)。
+		/// * どちらかが `NaN` の場合は `NaN` を、 `self==toward` の場合は `toward` を返します。
+		/// * `0.0` からは `toward` の符号を持つ最小の非正規化数に移ります。
+		fn next_after(self,toward:Self) -> Self;
+		/// 絶対値が `|self|` の浮動小数と、それより絶対値が1つ大きい浮動小数との間隔 (ULP) を返します。
+		/// * `0.0` の場合は最小の非正規化数を返します。
+		/// * 最大値 `MAX` の場合は1つ小さい浮動小数との間隔を返します。
+		/// * 無限大や `NaN` の場合は `NaN` を返します。
+		fn ulp(self) -> Self;
+	}
+
+	macro_rules! impl_ulps {
+		( $( $f:ident $i:ident )+ ) => { $(
+			impl FloatUlps for $f {
+				fn ulps_distance(self,other:Self) -> Option<u64> {
+					if self.is_nan() || other.is_nan() { return None; }
+					// 符号付き整数に変換した上で、負の値は順序が逆になるように変換する (-0.0 は 0 に移る)
+					let key = |x:$f| {
+						let b = x.to_bits() as $i;
+						if b<0 { $i::MIN - b } else { b }
+					};
+					let d = (key(self) as i128) - (key(other) as i128);
+					Some(d.unsigned_abs() as u64)
+				}
+				fn next_after(self,toward:Self) -> Self {
+					if self.is_nan() || toward.is_nan() { return $f::NAN; }
+					if self==toward { return toward; }
+					if self==0.0 { return $f::from_bits(1).copysign(toward); }
+					// 絶対値が大きくなる方向ならビット表現を1つ増やし、小さくなる方向なら1つ減らす
+					let bits = self.to_bits();
+					match (self<toward)==(self>0.0) {
+						true => $f::from_bits(bits+1),
+						false => $f::from_bits(bits-1)
+					}
+				}
+				fn ulp(self) -> Self {
+					if !self.is_finite() { return $f::NAN; }
+					let a = self.abs();
+					match a==$f::MAX {
+						true => a - a.next_after(0.0),
+						false => a.next_after($f::INFINITY) - a
+					}
+				}
+			}
+		)+ };
+	}
+	impl_ulps!( f64 i64 f32 i32 );
+
+	#[inline]
+	/// 2つの浮動小数の距離が `max_ulps` ULP 以内であるか判定します。
+	/// * `NaN` はどの値とも等しくないとみなします。
+	/// * `+0.0` と `-0.0` は等しいとみなします。
+	pub fn eq_ulps<T: FloatUlps>(a:T,b:T,max_ulps:u64) -> bool { a.eq_ulps(b,max_ulps) }
+
+	#[inline]
+	/// `x` から `toward` の方向に隣接する表現可能な浮動小数を返します。詳しくは `FloatUlps::next_after` を参照。
+	pub fn next_after<T: FloatUlps>(x:T,toward:T) -> T { x.next_after(toward) }
+
+	#[inline]
+	/// `x` における ULP (unit in the last place) を返します。詳しくは `FloatUlps::ulp` を参照。
+	pub fn ulp<T: FloatUlps>(x:T) -> T { x.ulp() }
+
+	#[cfg(test)]
+	#[test]
+	fn test_eq_ulps() {
+		let a = 1.0_f64;
+		let b = f64::from_bits(a.to_bits()+1);
+		assert_eq!( a.ulps_distance(b), Some(1) );
+		assert!( eq_ulps(a,b,1) );
+		assert!( !eq_ulps(a,b,0) );
+		assert!( !eq_ulps(1.0,1.0001,4) );
+		assert!( eq_ulps(0.1+0.2,0.3,1) );
+		assert!( eq_ulps(0.0_f32,-0.0,0) );
+		assert_eq!( f32::from_bits(1).ulps_distance(-f32::from_bits(1)), Some(2) );
+		assert!( !eq_ulps(f64::NAN,f64::NAN,u64::MAX) );
+		assert_eq!( f32::MAX.ulps_distance(f32::INFINITY), Some(1) );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_next_after_ulp() {
+		assert_eq!( next_after(1.0_f64,2.0).to_bits(), 0x3FF0_0000_0000_0001 );
+		assert_eq!( next_after(1.0_f64,0.0).to_bits(), 0x3FEF_FFFF_FFFF_FFFF );
+		assert_eq!( next_after(-1.0_f64,-2.0).to_bits(), 0xBFF0_0000_0000_0001 );
+		assert_eq!( next_after(0.0_f64,1.0).to_bits(), 0x0000_0000_0000_0001 );
+		assert_eq!( next_after(0.0_f64,-1.0).to_bits(), 0x8000_0000_0000_0001 );
+		assert_eq!( next_after(f64::from_bits(1),-1.0).to_bits(), 0 );
+		assert_eq!( next_after(f64::MAX,f64::INFINITY), f64::INFINITY );
+		assert_eq!( next_after(f64::INFINITY,0.0), f64::MAX );
+		assert_eq!( next_after(1.5_f32,1.5), 1.5 );
+		assert_eq!( next_after(1.0_f32,2.0).to_bits(), 0x3F80_0001 );
+		assert!( next_after(f32::NAN,1.0).is_nan() );
+
+		assert_eq!( ulp(1.0_f64), f64::EPSILON );
+		assert_eq!( ulp(-1.0_f64), f64::EPSILON );
+		assert_eq!( ulp(1.0_f32), f32::EPSILON );
+		assert_eq!( ulp(0.0_f64).to_bits(), 1 );
+		assert_eq!( ulp(f64::MAX), f64::MAX - next_after(f64::MAX,0.0) );
+		assert_eq!( ulp(f32::MAX).to_bits(), 0x7380_0000 );
+		assert!( ulp(f64::INFINITY).is_nan() );
+		assert!( ulp(f64::NAN).is_nan() );
+	}
+
+}
+pub use ulps::{FloatUlps,eq_ulps,next_after,ulp};
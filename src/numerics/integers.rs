@@ -0,0 +1,118 @@
+//! 整数に対する数論的な演算をまとめたモジュール
+
+use super::*;
+
+
+
+/// 複数の整数の最大公約数と最小公倍数
+mod gcd_lcm {
+	use super::*;
+
+	/// 整数のイテレータ (あるいは配列などの `IntoIterator`) の全要素の最大公約数・最小公倍数を計算する拡張トレイト
+	pub trait GcdLcm<T> {
+		/// 全要素の最大公約数を計算します。
+		/// * 2つずつ二進 GCD (Stein のアルゴリズム) で畳み込みます。符号付き整数の場合も結果は非負になります。
+		/// * 要素がない場合は単位元 `0` を返します。
+		fn gcd(self) -> T;
+		/// 全要素の最小公倍数を計算します。
+		/// * オーバーフローを避けるため `lcm(a,b) = a/gcd(a,b)*b` として畳み込みます。符号付き整数の場合も結果は非負になります。
+		/// * 要素がない場合は単位元 `1` を返し、 `0` を含む場合は `0` を返します。
+		fn lcm(self) -> T;
+	}
+
+	impl<T,I> GcdLcm<T> for I
+	where T: Integer, I: IntoIterator<Item=T>
+	{
+		fn gcd(self) -> T {
+			self.into_iter().fold(T::zero(),|a,b| a.gcd(&b) )
+		}
+
+		fn lcm(self) -> T {
+			self.into_iter().fold(T::one(),|a,b| a.lcm(&b) )
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_gcd_lcm() {
+		assert_eq!( [12,18,24].gcd(), 6 );
+		assert_eq!( [4,6].lcm(), 12 );
+		assert_eq!( vec![-12_i64,18].gcd(), 6 );
+		assert_eq!( [-4_i32,6].lcm(), 12 );
+		assert_eq!( [0_u32,5].gcd(), 5 );
+		assert_eq!( [0_u32,5].lcm(), 0 );
+		assert_eq!( Vec::<u64>::new().gcd(), 0 );
+		assert_eq!( Vec::<u64>::new().lcm(), 1 );
+		// 途中の積がオーバーフローしない
+		assert_eq!( [u32::MAX,u32::MAX].lcm(), u32::MAX );
+	}
+
+}
+pub use gcd_lcm::GcdLcm;
+
+
+
+/// 周期境界条件・鏡像境界条件で配列のインデクスを範囲内に収めるモジュール
+mod index_wrap {
+
+	/// 周期境界条件でインデクスを `0..len` の範囲に収めます (ユークリッド剰余)。
+	/// * `-1` は `len-1` に、 `len` は `0` になります。
+	/// * `len==0` の場合はパニックします。
+	pub fn wrap_index(i:isize,len:usize) -> usize {
+		if len==0 { panic!("長さ 0 の配列のインデクスは折り返せません"); }
+		(i as i128).rem_euclid(len as i128) as usize
+	}
+
+	/// 鏡像境界条件でインデクスを `0..len` の範囲に収めます。
+	/// * 端の要素を含めて折り返すため、 `-1` は `0` に、 `len` は `len-1` になります。周期は `2*len` です。
+	/// * `len==0` の場合はパニックします。
+	pub fn reflect_index(i:isize,len:usize) -> usize {
+		if len==0 { panic!("長さ 0 の配列のインデクスは折り返せません"); }
+		let period = 2*(len as i128);
+		let m = (i as i128).rem_euclid(period);
+		( if m<(len as i128) { m } else { period-1-m } ) as usize
+	}
+
+	/// 符号付き整数のインデクスを配列の範囲内に収めるメソッドを追加するトレイト
+	pub trait IndexWrap {
+		/// 周期境界条件でインデクスを `0..len` の範囲に収めます。 `wrap_index` を参照。
+		fn wrap_index(self,len:usize) -> usize;
+		/// 鏡像境界条件でインデクスを `0..len` の範囲に収めます。 `reflect_index` を参照。
+		fn reflect_index(self,len:usize) -> usize;
+	}
+
+	macro_rules! impl_index_wrap {
+		( $($t:ty),+ ) => { $(
+			impl IndexWrap for $t {
+				fn wrap_index(self,len:usize) -> usize {
+					wrap_index(self as isize,len)
+				}
+				fn reflect_index(self,len:usize) -> usize {
+					reflect_index(self as isize,len)
+				}
+			}
+		)+ };
+	}
+	impl_index_wrap!(isize,i32,i64);
+
+	#[cfg(test)]
+	#[test]
+	fn test_index_wrap() {
+		assert_eq!( wrap_index(-1,5), 4 );
+		assert_eq!( wrap_index(-6,5), 4 );
+		assert_eq!( wrap_index(5,5), 0 );
+		assert_eq!( wrap_index(12,5), 2 );
+		assert_eq!( wrap_index(isize::MIN,1), 0 );
+		assert_eq!( (-3_i32).wrap_index(4), 1 );
+
+		let r = (-4..9).map(|i| reflect_index(i,4) ).collect::<Vec<_>>();
+		assert_eq!( r, vec![3,2,1,0,0,1,2,3,3,2,1,0,0] );
+		assert_eq!( reflect_index(-1,1), 0 );
+		assert_eq!( 7_i64.reflect_index(3), 1 );
+
+		assert!( std::panic::catch_unwind(|| wrap_index(0,0) ).is_err() );
+		assert!( std::panic::catch_unwind(|| reflect_index(-1,0) ).is_err() );
+	}
+
+}
+pub use index_wrap::*;
@@ -11,6 +11,9 @@ pub use basic_operations::*;
 #[cfg(feature="numerics")]
 pub mod primitive_functions;
 
+#[cfg(feature="numerics")]
+pub mod special_functions;
+
 #[cfg(feature="numerics")]
 mod primitive_function_extensions;
 #[cfg(feature="numerics")]
@@ -20,3 +23,23 @@ pub use primitive_function_extensions::*;
 mod float;
 #[cfg(feature="numerics")]
 pub use float::*;
+
+#[cfg(feature="numerics")]
+mod statistics;
+#[cfg(feature="numerics")]
+pub use statistics::*;
+
+#[cfg(feature="numerics")]
+mod integers;
+#[cfg(feature="numerics")]
+pub use integers::*;
+
+#[cfg(feature="numerics")]
+mod activations;
+#[cfg(feature="numerics")]
+pub use activations::*;
+
+#[cfg(feature="ndarray")]
+mod array_operations;
+#[cfg(feature="ndarray")]
+pub use array_operations::*;
@@ -8,17 +8,182 @@ mod hypot_extension {
 		pub trait Iter<T> = IntoIterator<Item=T>;
 	}
 
+	/// 多数の要素の二乗和平方根 (root-sum-square) と二乗平均平方根 (root-mean-square) を計算するトレイト
+	pub trait RootSumSquare<T> {
+		/// 多数個の要素に対して平方和のルート `√(x1²+x2²+...)` を計算する。要素がない場合は `0` を返す。
+		/// * 最大の絶対値で割ったスケールで平方和を累積するため、途中でオーバーフロー/アンダーフローしにくい。
+		fn rss(self) -> T;
+		/// 多数個の要素に対して二乗平均のルート `√((x1²+x2²+...)/n)` を計算する。要素がない場合は `None` を返す。
+		fn rms(self) -> Option<T>;
+	}
+	impl<T:Float, I:Iter<T>> RootSumSquare<T> for I {
+		fn rss(self) -> T {
+			let (scale,ssq,_) = scaled_sum_square(self);
+			scale * ssq.sqrt()
+		}
+		fn rms(self) -> Option<T> {
+			let (scale,ssq,n) = scaled_sum_square(self);
+			(n>0).then(|| scale * (ssq/T::from(n).unwrap()).sqrt() )
+		}
+	}
+
+	/// 平方和を `scale² * ssq` の形で累積し、 `(scale,ssq,要素数)` を返す
+	/// * `ssq` の累積には Neumaier の補償加算を用い、多数の小さな値を足しても丸め誤差が蓄積しないようにする。
+	fn scaled_sum_square<T:Float>(iter:impl Iter<T>) -> (T,T,usize) {
+		let mut scale = T::zero();
+		let mut ssq = T::one();
+		let mut compensation = T::zero();
+		let mut n = 0;
+		let mut infinite = false;
+		for x in iter {
+			n += 1;
+			let a = x.abs();
+			if a.is_infinite() { infinite = true; }
+			else if a.is_nan() { return (a,T::one(),n); }
+			else if a>T::zero() {
+				if scale<a {
+					let r = (scale/a).powi(2);
+					(ssq,compensation) = compensated_add(ssq*r,compensation*r,T::one());
+					scale = a;
+				}
+				else { (ssq,compensation) = compensated_add(ssq,compensation,(a/scale).powi(2)); }
+			}
+		}
+		if infinite { (T::infinity(),T::one(),n) }
+		else { (scale,ssq+compensation,n) }
+	}
+
+	/// Neumaier の補償加算により `sum+x` を計算し、新たな和と補償項を返す
+	#[inline]
+	pub(super) fn compensated_add<T:Float>(sum:T,compensation:T,x:T) -> (T,T) {
+		let t = sum+x;
+		let c = if sum.abs()>=x.abs() { (sum-t)+x } else { (x-t)+sum };
+		(t,compensation+c)
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_rss_rms() {
+		assert_eq!( [3.0,4.0].rss(), 5.0 );
+		assert_eq!( [1.0,2.0,2.0].rss(), 3.0 );
+		assert_eq!( Vec::<f64>::new().rss(), 0.0 );
+		assert_eq!( [3.0,-4.0].rms(), Some((12.5f64).sqrt()) );
+		assert_eq!( [2.0f32;4].rms(), Some(2.0) );
+		assert_eq!( Vec::<f64>::new().rms(), None );
+		// 素朴に二乗するとオーバーフロー/アンダーフローする値
+		assert!( ([3e200,4e200].rss()/5e200-1.0).abs() < 1e-15 );
+		assert!( ([3e-200,4e-200].rss()/5e-200-1.0).abs() < 1e-15 );
+		assert_eq!( [1.0,f64::INFINITY].rss(), f64::INFINITY );
+		assert!( [1.0,f64::NAN].rss().is_nan() );
+	}
+
+	/// 実数と複素数の絶対値を共通に扱うトレイト (`HypotForArray` の要素の型)
+	pub trait Magnitude {
+		/// 絶対値の型
+		type Real: Float;
+		/// 絶対値 (複素数の場合はノルム)
+		fn magnitude(self) -> Self::Real;
+	}
+	macro_rules! impl_magnitude {
+		( $($t:ty)+ ) => { $(
+			impl Magnitude for $t {
+				type Real = $t;
+				#[inline]
+				fn magnitude(self) -> $t { self.abs() }
+			}
+			impl Magnitude for Complex<$t> {
+				type Real = $t;
+				#[inline]
+				fn magnitude(self) -> $t { self.norm() }
+			}
+		)+ };
+	}
+	impl_magnitude!( f64 f32 );
+
 	/// `hypot` 関数を多数の要素でも使えるようにするトレイト
-	pub trait HypotForArray<T> {
-		/// 多数個の要素に対して平方和のルートを計算する
-		fn hypot(self) -> T;
+	/// * 実数 (`f64`, `f32`) と複素数 (`Complex<f64>`, `Complex<f32>`) の要素に対応し、複素数の要素に対しては各要素のノルムを用いる。
+	pub trait HypotForArray<R> {
+		/// 多数個の要素に対して平方和のルートを計算する。2引数の `hypot` を順に適用する。
+		fn hypot(self) -> R;
+		/// 多数個の要素に対して平方和のルート `√(|x1|²+|x2|²+...)` を計算する。要素がない場合は `0` を返す。
+		/// * `hypot` と異なり、最大の絶対値でスケールした平方和を1回の走査で累積するので、要素数が多くても丸め誤差が蓄積しにくく、オーバーフローもしない。
+		fn hypot_all(self) -> R;
 	}
-	impl<T:Float, I:Iter<T>> HypotForArray<T> for I {
-		fn hypot(self) -> T {
+	impl<M:Magnitude, I:IntoIterator<Item=M>> HypotForArray<M::Real> for I {
+		fn hypot(self) -> M::Real {
 			self.into_iter()
+			.map(M::magnitude)
 			.reduce( |a,v| a.hypot(v) )
-			.unwrap_or(T::zero())
+			.unwrap_or(M::Real::zero())
+		}
+		fn hypot_all(self) -> M::Real {
+			self.into_iter().map(M::magnitude).rss()
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_hypot_all() {
+		// 厳密値は √(10⁸+10⁶·10⁻²)=√(100010000)
+		let v = std::iter::once(1e4).chain(std::iter::repeat_n(0.1,1_000_000)).collect::<Vec<f64>>();
+		let exact = 100_010_000.0_f64.sqrt();
+		let all = v.iter().copied().hypot_all();
+		let pairwise = v.iter().copied().hypot();
+		assert!( ((all-exact)/exact).abs()<1e-15 );
+		assert!( (all-exact).abs()<=(pairwise-exact).abs() );
+
+		assert_eq!( [3e300,4e300].hypot_all(), 5e300 );
+		assert_eq!( [Complex::new(3.0,4.0),Complex::new(0.0,-12.0)].hypot_all(), 13.0 );
+		assert_eq!( [Complex::new(1.0_f32,2.0),Complex::new(2.0,4.0)].hypot_all(), 5.0 );
+		assert_eq!( Vec::<Complex<f64>>::new().hypot_all(), 0.0 );
+
+		// 2引数の hypot を順に適用する方も複素数に対応する
+		assert_eq!( [Complex::new(3.0,4.0),Complex::new(0.0,-12.0)].hypot(), 13.0 );
+		assert_eq!( [3.0,4.0,12.0].hypot(), 13.0 );
+		assert_eq!( Vec::<Complex<f32>>::new().hypot(), 0.0 );
+	}
+
+	/// 多数の要素をノルムが `1` になるように正規化するトレイト
+	pub trait Normalize<T> {
+		/// ユークリッドノルム (`rss`) が `1` になるように各要素を割った `Vec` を返す。ノルムが `0` の場合は元の値をそのまま返す。
+		fn normalize(self) -> Vec<T>;
+		/// L1 ノルム (絶対値の和) が `1` になるように各要素を割った `Vec` を返す。ノルムが `0` の場合は元の値をそのまま返す。
+		fn normalize_l1(self) -> Vec<T>;
+	}
+	impl<T:Float, I:Iter<T>> Normalize<T> for I {
+		fn normalize(self) -> Vec<T> {
+			let v = self.into_iter().collect::<Vec<_>>();
+			let norm = v.iter().copied().rss();
+			divide_by_norm(v,norm)
+		}
+		fn normalize_l1(self) -> Vec<T> {
+			let v = self.into_iter().collect::<Vec<_>>();
+			let norm = v.iter().fold(T::zero(),|s,x| s+x.abs() );
+			divide_by_norm(v,norm)
+		}
+	}
+
+	/// 各要素をノルムで割る。ノルムが `0` の場合は何もしない。
+	fn divide_by_norm<T:Float>(mut v:Vec<T>,norm:T) -> Vec<T> {
+		if !norm.is_zero() {
+			v.iter_mut().for_each(|x| *x = *x/norm );
 		}
+		v
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_normalize() {
+		let n = [3.0,-4.0].normalize();
+		assert_eq!( n, vec![0.6,-0.8] );
+		let n = (1..=10).map(|i| i as f64 ).normalize();
+		assert!( (n.iter().copied().rss()-1.0).abs()<1e-15 );
+		let n = [1e300_f64,2e300,-2e300].normalize();
+		assert!( (n.iter().copied().rss()-1.0).abs()<1e-15 );
+		assert_eq!( [1.0_f32,-3.0,4.0].normalize_l1(), vec![0.125,-0.375,0.5] );
+		assert_eq!( [0.0,0.0].normalize(), vec![0.0,0.0] );
+		assert_eq!( [0.0_f32;3].normalize_l1(), vec![0.0;3] );
+		assert_eq!( Vec::<f64>::new().normalize(), Vec::<f64>::new() );
 	}
 
 	/// `hypot` 関数を多数の要素でも使えるようにするトレイト
@@ -89,6 +254,58 @@ pub use mul_add_extension::*;
 
 
 
+/// 線形補間に関する関数を定義するモジュール
+mod interpolation {
+	use super::*;
+	use primitive_functions::mul_add;
+	use primitive_functions::float_misc::MulAdd;
+
+	/// `a` と `b` の間を `t` で線形補間した値 `a+(b-a)t` を計算します。 `mul_add` により丸め誤差を抑えます。
+	/// * `t` が `[0,1]` の範囲外の場合は外挿となります。
+	#[inline]
+	pub fn lerp<F: Float + MulAdd>(a:F,b:F,t:F) -> F {
+		mul_add(b-a,t,a)
+	}
+
+	/// `lerp` の逆関数で、 `v` が `a` と `b` の間のどの位置にあるかを表す `t=(v-a)/(b-a)` を計算します。
+	/// * `a==b` の場合は無限大または NaN を返します。
+	#[inline]
+	pub fn inverse_lerp<F: Float>(a:F,b:F,v:F) -> F {
+		(v-a)/(b-a)
+	}
+
+	/// 範囲 `[in_lo,in_hi]` における `v` の位置を、範囲 `[out_lo,out_hi]` の対応する位置に写します。
+	/// * `inverse_lerp` と `lerp` を合成したもので、範囲外の値は外挿されます。
+	#[inline]
+	pub fn remap<F: Float + MulAdd>(v:F,in_lo:F,in_hi:F,out_lo:F,out_hi:F) -> F {
+		lerp(out_lo,out_hi,inverse_lerp(in_lo,in_hi,v))
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_interpolation() {
+		assert_eq!( lerp(2.0,6.0,0.0), 2.0 );
+		assert_eq!( lerp(2.0,6.0,1.0), 6.0 );
+		assert_eq!( lerp(2.0,6.0,0.5), 4.0 );
+		assert_eq!( lerp(2.0_f32,6.0,1.5), 8.0 );
+		assert_eq!( lerp(2.0,6.0,-0.25), 1.0 );
+
+		assert_eq!( inverse_lerp(2.0,6.0,4.0), 0.5 );
+		assert_eq!( inverse_lerp(2.0,6.0,2.0), 0.0 );
+		assert_eq!( inverse_lerp(2.0,6.0,7.0), 1.25 );
+		assert!( inverse_lerp(1.0,1.0,1.0_f64).is_nan() );
+
+		assert_eq!( remap(5.0,0.0,10.0,100.0,200.0), 150.0 );
+		assert_eq!( remap(-5.0,0.0,10.0,100.0,200.0), 50.0 );
+		assert_eq!( remap(32.0_f64,32.0,212.0,0.0,100.0), 0.0 );
+		assert_eq!( remap(212.0_f64,32.0,212.0,0.0,100.0), 100.0 );
+	}
+
+}
+pub use interpolation::{lerp,inverse_lerp,remap};
+
+
+
 /// 多項式の計算を効率よく行う `eval_poly` を定義するモジュール
 mod evaluate_polynomials {
 	use super::*;
@@ -250,3 +467,199 @@ mod evaluate_polynomials {
 
 }
 pub use evaluate_polynomials::eval_poly;
+
+
+
+/// 対数領域での和 `log(exp(a)+exp(b)+...)` を数値的に安定に計算するモジュール
+mod log_sum_exp {
+	use super::*;
+
+	/// `log(exp(a)+exp(b))` を `max(a,b) + ln(1+exp(-|a-b|))` としてオーバーフローせずに計算します。
+	pub fn logaddexp<T:Float>(a:T,b:T) -> T {
+		if a.is_nan() || b.is_nan() { return T::nan(); }
+		let (max,min) = if a>b { (a,b) } else { (b,a) };
+		// 両方が同じ符号の無限大の場合に `inf-inf` とならないようにする
+		if max.is_infinite() || min==T::neg_infinity() { return max; }
+		max + (min-max).exp().ln_1p()
+	}
+
+	/// 多数の要素に対して `log(Σexp(x))` を数値的に安定に計算するトレイト
+	pub trait LogSumExp<T> {
+		/// `log(exp(x1)+exp(x2)+...)` を最大値で割った形で累積し、オーバーフローせずに計算する。要素がない場合は `-∞` を返す。
+		fn logsumexp(self) -> T;
+	}
+	impl<T:Float, I:IntoIterator<Item=T>> LogSumExp<T> for I {
+		fn logsumexp(self) -> T {
+			// Σexp(x) = exp(max) * sum として1回の走査で累積する
+			let mut max = T::neg_infinity();
+			let mut sum = T::zero();
+			for x in self {
+				if x.is_nan() { return x; }
+				if x==T::infinity() { max = x; sum = T::one(); }
+				else if max==T::infinity() || x==T::neg_infinity() {}
+				else if x>max {
+					sum = sum * (max-x).exp() + T::one();
+					max = x;
+				}
+				else { sum = sum + (x-max).exp(); }
+			}
+			if max.is_infinite() { max }
+			else { max + sum.ln() }
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_logsumexp() {
+		let close = |a:f64,b:f64| (a-b).abs()<=1e-14*b.abs().max(1.0) ;
+		let naive = |v:&[f64]| v.iter().map(|x| x.exp() ).sum::<f64>().ln() ;
+		assert!( close(logaddexp(0.5,-1.25),naive(&[0.5,-1.25])) );
+		assert!( close(logaddexp(1000.0,1001.0),1001.0+(-1.0_f64).exp().ln_1p()) );
+		assert_eq!( logaddexp(f64::NEG_INFINITY,f64::NEG_INFINITY), f64::NEG_INFINITY );
+		assert_eq!( logaddexp(f64::NEG_INFINITY,2.0), 2.0 );
+		assert_eq!( logaddexp(f64::INFINITY,f64::INFINITY), f64::INFINITY );
+
+		let v = [0.3,-2.0,1.7,0.0,1.7];
+		assert!( close(v.logsumexp(),naive(&v)) );
+		assert!( close([1000.0,1001.0].logsumexp(),logaddexp(1000.0,1001.0)) );
+		assert!( close([-1000.0,-1001.0].logsumexp(),logaddexp(-1000.0,-1001.0)) );
+		assert!( ([1.0_f32,2.0].logsumexp()-logaddexp(1.0_f32,2.0)).abs()<1e-6 );
+		assert_eq!( Vec::<f64>::new().logsumexp(), f64::NEG_INFINITY );
+		assert_eq!( [f64::NEG_INFINITY,3.0].logsumexp(), 3.0 );
+		assert_eq!( [1.0,f64::INFINITY,2.0].logsumexp(), f64::INFINITY );
+		assert!( [1.0,f64::NAN].logsumexp().is_nan() );
+	}
+
+}
+pub use log_sum_exp::*;
+
+
+
+/// 丸め誤差を補償しながら総和を計算するモジュール
+mod compensated_sum {
+	use super::*;
+	use hypot_extension::compensated_add;
+
+	/// 浮動小数点数のイテレータに対して、丸め誤差を補償した総和を計算するメソッドを提供するトレイト
+	/// * 要素がない場合は `0` を返します。
+	pub trait CompensatedSum<T> {
+		/// Kahan の補償加算により総和を計算する。
+		/// * 各加算で失われた下位の桁を補償項に保持し、次の要素に加えることで、誤差が要素数に比例して蓄積しないようにする。
+		/// * 加える値が途中の和より大きい場合は補償できないので、その場合は `neumaier_sum` を用いる。
+		fn kahan_sum(self) -> T;
+		/// Neumaier の補償加算により総和を計算する。
+		/// * Kahan の補償加算を改良したもので、加える値が途中の和より大きい場合も正しく補償する。
+		fn neumaier_sum(self) -> T;
+	}
+	impl<T:Float, I:IntoIterator<Item=T>> CompensatedSum<T> for I {
+		fn kahan_sum(self) -> T {
+			let (sum,_) = self.into_iter()
+			.fold((T::zero(),T::zero()),|(sum,c),x| {
+				let y = x-c;
+				let t = sum+y;
+				(t,(t-sum)-y)
+			});
+			sum
+		}
+		fn neumaier_sum(self) -> T {
+			let (sum,c) = self.into_iter()
+			.fold((T::zero(),T::zero()),|(sum,c),x| compensated_add(sum,c,x) );
+			sum+c
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_compensated_sum() {
+		let v = [0.1;10];
+		assert_ne!( v.iter().sum::<f64>(), 1.0 );
+		assert_eq!( v.kahan_sum(), 1.0 );
+		assert_eq!( v.neumaier_sum(), 1.0 );
+
+		// 途中の和より大きな値を加える場合は Neumaier のみが正しく補償できる
+		let v = [1e16,1.0,-1e16];
+		assert_eq!( v.iter().sum::<f64>(), 0.0 );
+		assert_eq!( v.neumaier_sum(), 1.0 );
+
+		let v = (0..10000).map(|i| if i%2==0 { 1e-3 } else { 1e8 } ).collect::<Vec<f64>>();
+		assert_eq!( v.iter().copied().kahan_sum(), 5e11+5.0 );
+		assert_eq!( v.iter().copied().neumaier_sum(), 5e11+5.0 );
+
+		assert_eq!( Vec::<f64>::new().kahan_sum(), 0.0 );
+		assert_eq!( Vec::<f32>::new().neumaier_sum(), 0.0 );
+	}
+
+}
+pub use compensated_sum::CompensatedSum;
+
+
+
+/// 整数乗 `powi` を `f32`, `f64`, `Complex` に対して統一的に扱うモジュール
+mod powi_extension {
+	use super::*;
+	use std::ops::Neg;
+
+	/// 関数 `powi` の底として受け入れ可能な型を定義するトレイト
+	pub trait SupportsPowi: Clone + One {
+		/// 小さな指数の高速経路を通らない場合に使用する整数乗
+		fn powi_impl(self,n:i32) -> Self;
+	}
+
+	macro_rules! powi_impl {
+		( $($t:ty),+ ) => { $(
+			impl SupportsPowi for $t {
+				#[inline]
+				fn powi_impl(self,n:i32) -> Self { <$t>::powi(self,n) }
+			}
+		)+ };
+	}
+	powi_impl!(f32,f64);
+
+	impl<T> SupportsPowi for Complex<T>
+	where T: Clone + Num + Neg<Output=T>
+	{
+		#[inline]
+		fn powi_impl(self,n:i32) -> Self { Complex::powi(&self,n) }
+	}
+
+	/// `base` の `n` 乗を計算します。
+	/// * `n` が `0..=4` の場合は繰り返しの乗算で計算するため、 `powi(x,2)` は `x*x` と厳密に一致します。
+	/// * それ以外の場合は各型の `powi` を呼び出します。
+	#[inline]
+	pub fn powi<T:SupportsPowi>(base:T,n:i32) -> T {
+		match n {
+			0 => T::one(),
+			1 => base,
+			2 => base.clone()*base,
+			3 => base.clone()*base.clone()*base,
+			4 => base.clone()*base.clone()*base.clone()*base,
+			_ => base.powi_impl(n)
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_powi() {
+		let xs = [0.1_f64,1.0/3.0,-2.7,1.1,std::f64::consts::PI,1e-150,123456.789];
+		for &x in xs.iter() {
+			assert_eq!( powi(x,0), 1.0 );
+			assert_eq!( powi(x,1), x );
+			assert_eq!( powi(x,2), x*x );
+			assert_eq!( powi(x,3), x*x*x );
+			assert_eq!( powi(x,4), x*x*x*x );
+			assert_eq!( powi(x,-2), x.powi(-2) );
+			assert_eq!( powi(x,7), x.powi(7) );
+		}
+		assert_eq!( powi(1.1_f32,2), 1.1_f32*1.1_f32 );
+		assert_eq!( powi(f64::NAN,0), 1.0 );
+
+		let z = Complex::new(0.3_f64,-1.7);
+		assert_eq!( powi(z,0), Complex::new(1.0,0.0) );
+		assert_eq!( powi(z,2), z*z );
+		assert_eq!( powi(z,3), z*z*z );
+		assert_eq!( powi(z,-1), z.powi(-1) );
+		assert_eq!( powi(Complex::new(0_i32,1),6), Complex::new(-1,0) );
+	}
+
+}
+pub use powi_extension::{SupportsPowi,powi};
@@ -18,6 +18,10 @@ mod exp_log {
 	pub trait Exponential: Sized {
 		fn exp_impl(self) -> Self;
 	}
+	/// `Float` と `Complex` に対して `exp10` に対応するトレイト
+	pub trait Exponential10: Sized {
+		fn exp10_impl(self) -> Self;
+	}
 
 	macro_rules! log_impl {
 		( $t:ty, $b:ty ) => {
@@ -40,6 +44,20 @@ mod exp_log {
 	log_impl!( C<f64>, f64 );
 	log_impl!( C<f32>, f32 );
 
+	macro_rules! exp10_impl {
+		( $( $t:ty : $b:ty ),+ ) => { $(
+			impl Exponential10 for $t {
+				#[inline]
+				fn exp10_impl(self) -> $t { (10.0 as $b).powf(self) }
+			}
+			impl Exponential10 for C<$t> {
+				#[inline]
+				fn exp10_impl(self) -> C<$t> { self.expf(10.0) }
+			}
+		)+ };
+	}
+	exp10_impl!( f64: f64, f32: f32 );
+
 	#[inline]
 	pub fn log<T,B>(x:T,base:B) -> T where T: Logarithm<B> {
 		x.log_impl(base)
@@ -52,6 +70,11 @@ mod exp_log {
 	pub fn exp<T>(x:T) -> T where T: Exponential {
 		x.exp_impl()
 	}
+	/// `10^x` を計算します。複素数の場合は `exp(x·ln10)` です。
+	#[inline]
+	pub fn exp10<T>(x:T) -> T where T: Exponential10 {
+		x.exp10_impl()
+	}
 
 	macro_rules! functions {
 		( $($name:ident)+ ) => { $(
@@ -63,8 +86,26 @@ mod exp_log {
 	}
 	functions!( log2 log10 ln_1p exp2 exp_m1 );
 
+	#[cfg(test)]
+	#[test]
+	fn test_exp10() {
+		assert_eq!( exp10(2.0_f64), 100.0 );
+		assert_eq!( exp10(-1.0_f32), 0.1 );
+		for x in [0.001,0.5,3.0,12345.0] {
+			assert!( (exp10(log10(x))/x-1.0_f64).abs()<1e-15 );
+		}
+
+		// 複素数の log は主値 ln(z)/ln(b) で、 exp10 と整合する
+		let z = C::new(-3.0_f64,4.0);
+		let l = log(z,10.0);
+		assert!( (l-ln(z)/10.0_f64.ln()).norm()<1e-15 );
+		assert!( l.im>0.0 && l.im<std::f64::consts::PI );
+		assert!( (exp10(l)-z).norm()<1e-14 );
+		assert!( (exp10(C::new(2.0_f64,0.0))-C::new(100.0,0.0)).norm()<1e-12 );
+	}
+
 }
-pub use exp_log::{log,ln,log2,log10,ln_1p,exp,exp2,exp_m1};
+pub use exp_log::{log,ln,log2,log10,ln_1p,exp,exp2,exp10,exp_m1};
 
 /// 2乗根、3乗根、n乗根を定義するモジュール
 pub(crate) mod root {
@@ -79,6 +120,8 @@ pub(crate) mod root {
 	/// 1のn乗根の値を計算するトレイト
 	pub trait Unit: Sized {
 		fn unit(n:usize) -> Vec<Self>;
+		/// 1のn乗根のうち k 番目の値のみを計算する
+		fn unit_at(n:usize,k:usize) -> Self;
 	}
 
 	/// `Float` と `Complex` に対して `sqrt_all`, `cbrt_all` に対応するトレイト
@@ -86,6 +129,9 @@ pub(crate) mod root {
 		fn sqrt_all(self) -> [C;2];
 		fn cbrt_all(self) -> [C;3];
 		fn root_all<const N:usize>(self) -> [C;N];
+		fn sqrt_branch(self,k:usize) -> C;
+		fn cbrt_branch(self,k:usize) -> C;
+		fn root_branch<const N:usize>(self,k:usize) -> C;
 	}
 
 	macro_rules! impl_root {
@@ -107,12 +153,13 @@ pub(crate) mod root {
 
 			impl Unit for C<$f> {
 				fn unit(n:usize) -> Vec<Self> {
-					use std::$f::consts::TAU;
-					(0..n).map(|i| {
-						C::from_polar(1.0, (i as $f)/(n as $f)*TAU )
-					})
+					(0..n).map(|i| Self::unit_at(n,i) )
 					.collect::<Vec<_>>()
 				}
+				fn unit_at(n:usize,k:usize) -> Self {
+					use std::$f::consts::TAU;
+					C::from_polar(1.0, ((k%n) as $f)/(n as $f)*TAU )
+				}
 			}
 
 			impl RootAll<C<$f>> for C<$f> {
@@ -139,6 +186,19 @@ pub(crate) mod root {
 					.collect::<Vec<_>>()
 					.try_into().unwrap()
 				}
+				fn sqrt_branch(self,k:usize) -> C<$f> {
+					let p = self.sqrt();
+					if k%2==0 { p } else { -p }
+				}
+				fn cbrt_branch(self,k:usize) -> C<$f> {
+					match k%3 {
+						0 => self.cbrt(),
+						k => self.cbrt() * Self::unit_at(3,k)
+					}
+				}
+				fn root_branch<const N:usize>(self,k:usize) -> C<$f> {
+					self.powf(1.0/(N as $f)) * Self::unit_at(N,k)
+				}
 			}
 
 			impl RootAll<C<$f>> for $f {
@@ -154,6 +214,18 @@ pub(crate) mod root {
 				fn root_all<const N:usize>(self) -> [C<$f>;N] {
 					C {re:self,im:0.0}.root_all::<N>()
 				}
+				#[inline]
+				fn sqrt_branch(self,k:usize) -> C<$f> {
+					C {re:self,im:0.0}.sqrt_branch(k)
+				}
+				#[inline]
+				fn cbrt_branch(self,k:usize) -> C<$f> {
+					C {re:self,im:0.0}.cbrt_branch(k)
+				}
+				#[inline]
+				fn root_branch<const N:usize>(self,k:usize) -> C<$f> {
+					C {re:self,im:0.0}.root_branch::<N>(k)
+				}
 			}
 
 		)+ };
@@ -184,9 +256,66 @@ pub(crate) mod root {
 	/// * 全ての根を計算します。
 	/// * 入力値が実数であっても、対応する複素数型を返します。
 	pub fn root_all<INPUT,ROOT,const N:usize>(x:INPUT) -> [ROOT;N] where INPUT: RootAll<ROOT> { x.root_all::<N>() }
+	#[inline]
+	/// 平方根を計算します。 `Float` と `Complex` に対応します。
+	/// * `sqrt_all` の `k` 番目の根のみを計算します。 `k` は2を法として扱います。
+	/// * 入力値が実数であっても、対応する複素数型を返します。
+	pub fn sqrt_branch<INPUT,ROOT>(x:INPUT,k:usize) -> ROOT where INPUT: RootAll<ROOT> { x.sqrt_branch(k) }
+	#[inline]
+	/// 立方根を計算します。 `Float` と `Complex` に対応します。
+	/// * `cbrt_all` の `k` 番目の根のみを計算します。 `k` は3を法として扱います。
+	/// * 入力値が実数であっても、対応する複素数型を返します。
+	pub fn cbrt_branch<INPUT,ROOT>(x:INPUT,k:usize) -> ROOT where INPUT: RootAll<ROOT> { x.cbrt_branch(k) }
+	#[inline]
+	/// n乗根を計算します。 `Float` と `Complex` に対応します。
+	/// * `root_all` の `k` 番目の根のみを計算します。 `k` は `N` を法として扱います。
+	/// * 入力値が実数であっても、対応する複素数型を返します。
+	pub fn root_branch<INPUT,ROOT,const N:usize>(x:INPUT,k:usize) -> ROOT where INPUT: RootAll<ROOT> { x.root_branch::<N>(k) }
+
+	/// 実数 `x` の実数の n乗根のみを全て返します。
+	/// * `root_all` の結果のうち、虚部が根の大きさに比例した許容誤差以内に収まるものの実部を返します。
+	/// * 奇数乗根では1つ、偶数乗根では正の値に対して2つ (正, 負の順)、負の値に対しては0個の根を返します。
+	/// * 実部は Newton 法で1回補正するので、 `8` の立方根は正確に `2` となります。
+	pub fn real_roots<const N:usize>(x:f64) -> Vec<f64> {
+		if x==0.0 { return vec![0.0]; }
+		let n = N as f64;
+		let tolerance = 16.0 * f64::EPSILON * x.abs().powf(1.0/n);
+		root_all::<_,C<f64>,N>(x).into_iter()
+		.filter(|r| r.im.abs()<=tolerance )
+		.map(|r| r.re - (r.re.powi(N as i32)-x)/(n*r.re.powi(N as i32-1)) )
+		.collect()
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_branch() {
+		let z = C { re: -3.0_f64, im: 4.0 };
+		assert_eq!( sqrt_branch(z,1), -sqrt_branch::<_,C<f64>>(z,0) );
+		assert_eq!( sqrt_branch(z,0), sqrt(z) );
+		assert_eq!( sqrt_branch(z,3), sqrt_branch::<_,C<f64>>(z,1) );
+		for (k,r) in cbrt_all(z).into_iter().enumerate() {
+			assert_eq!( cbrt_branch(z,k), r );
+		}
+		for (k,r) in root_all::<_,_,5>(z).into_iter().enumerate() {
+			assert_eq!( root_branch::<_,_,5>(z,k), r );
+		}
+		assert_eq!( sqrt_branch::<_,C<f64>>(-4.0,1), C { re: 0.0, im: -2.0 } );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_real_roots() {
+		assert_eq!( real_roots::<3>(8.0), vec![2.0] );
+		assert_eq!( real_roots::<3>(-8.0), vec![-2.0] );
+		assert_eq!( real_roots::<2>(9.0), vec![3.0,-3.0] );
+		assert_eq!( real_roots::<4>(-16.0), vec![] );
+		assert_eq!( real_roots::<5>(-1e10), vec![-100.0] );
+		assert_eq!( real_roots::<3>(0.0), vec![0.0] );
+		assert_eq!( real_roots::<1>(-1.5), vec![-1.5] );
+	}
 
 }
-pub use root::{sqrt,cbrt,sqrt_all,cbrt_all,root_all};
+pub use root::{sqrt,cbrt,sqrt_all,cbrt_all,root_all,sqrt_branch,cbrt_branch,root_branch,real_roots};
 
 /// 三角関数に対する関数定義をまとめて行うマクロ
 macro_rules! trig {
@@ -267,7 +396,53 @@ misc! {
 	atan2(y,x) as Atan2
 	hypot(y,x) as Hypot
 	mul_add(a,b,c) as MulAdd
+	copysign(x,sign) as CopySign
+	abs(x) as Abs
+	recip(x) as Recip
+}
+
+/// 符号関数 `signum` を定義するモジュール
+mod signum {
+
+	/// 浮動小数と符号付き整数に対して `signum` に対応するトレイト
+	pub trait Signum: Sized {
+		fn signum_impl(self) -> Self;
+	}
+
+	macro_rules! impl_signum {
+		( $($t:ty)+ ) => { $(
+			impl Signum for $t {
+				#[inline]
+				fn signum_impl(self) -> Self { self.signum() }
+			}
+		)+ };
+	}
+	impl_signum!( f64 f32 i8 i16 i32 i64 i128 isize );
+
+	/// 値の符号を返します。
+	/// * 浮動小数では IEEE 754 に従い、 `+0.0` や正の値は `1.0` 、 `-0.0` や負の値は `-1.0` 、 NaN は NaN を返します。
+	/// * 符号付き整数では `1`, `0`, `-1` のいずれかを返します。
+	#[inline]
+	pub fn signum<T: Signum>(x:T) -> T { x.signum_impl() }
+
+	#[cfg(test)]
+	#[test]
+	fn test_signum() {
+		use super::{copysign,abs,recip};
+		assert_eq!( signum(-0.0_f64), -1.0 );
+		assert_eq!( signum(0.0_f32), 1.0 );
+		assert!( signum(f64::NAN).is_nan() );
+		assert_eq!( (signum(-7_i32),signum(0_i64),signum(3_i8)), (-1,0,1) );
+		assert_eq!( copysign(3.0,-1.0), -3.0 );
+		assert_eq!( copysign(-3.0_f32,0.0), 3.0 );
+		assert!( copysign(0.0_f64,-0.0).is_sign_negative() );
+		assert_eq!( abs(-2.5_f64), 2.5 );
+		assert_eq!( recip(4.0_f32), 0.25 );
+		assert_eq!( recip(-0.0_f64), f64::NEG_INFINITY );
+	}
+
 }
+pub use signum::signum;
 
 /// `clamp` 関数を拡張した形で実装するモジュール
 mod clamp {
@@ -323,8 +498,75 @@ mod clamp {
 		x.clamp_impl(val1,val2)
 	}
 
+	/// 値 `x` を `[0,1]` の範囲に制限します。 `clamp(x,0.0,1.0)` と同等で、 `NaN` はそのまま返します。
+	#[inline]
+	pub fn clamp01<T: Float + Clamp>(x:T) -> T {
+		clamp(x,T::zero(),T::one())
+	}
+
+	/// `clamp01` の別名です。
+	#[inline]
+	pub fn saturate<T: Float + Clamp>(x:T) -> T {
+		clamp01(x)
+	}
+
+	/// 値 `x` を `[0,∞]` の範囲に制限します。 `max(x,0)` と異なり、 `NaN` はそのまま返します。
+	#[inline]
+	pub fn clamp_nonneg<T: Float + Clamp>(x:T) -> T {
+		clamp(x,T::zero(),T::infinity())
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_clamp01() {
+		assert_eq!( clamp01(-0.5), 0.0 );
+		assert_eq!( clamp01(0.25), 0.25 );
+		assert_eq!( clamp01(1.5_f32), 1.0 );
+		assert!( clamp01(f64::NAN).is_nan() );
+		assert_eq!( saturate(-2.0), 0.0 );
+		assert_eq!( saturate(0.75_f32), 0.75 );
+		assert_eq!( saturate(3.0), 1.0 );
+		assert!( saturate(f32::NAN).is_nan() );
+		assert_eq!( clamp_nonneg(-1.0), 0.0 );
+		assert_eq!( clamp_nonneg(0.5), 0.5 );
+		assert_eq!( clamp_nonneg(1e300), 1e300 );
+		assert_eq!( clamp_nonneg(f64::INFINITY), f64::INFINITY );
+		assert!( clamp_nonneg(f64::NAN).is_nan() );
+	}
+
+	/// `Ord` を実装する整数などの型に clamp を実装するトレイト
+	/// * `Float` に対する `Clamp` との実装の衝突を避けるため、別のトレイトとして定義しています。
+	pub trait ClampOrd: Sized {
+		fn clamp_impl(self,val1:Self,val2:Self) -> Self;
+	}
+
+	impl<T: Ord+Copy> ClampOrd for T {
+		fn clamp_impl(self,val1:Self,val2:Self) -> Self {
+			self.clamp(val1.min(val2),val1.max(val2))
+		}
+	}
+
+	/// 値 `x` を `val1` と `val2` の間に制限します。 `Ord` を実装する型に対応します。
+	/// * `Ord::clamp` とは異なり、境界の大小が逆でもパニックせず、入れ替えて扱います。
+	#[inline]
+	pub fn clamp_ord<T: ClampOrd>(x:T,val1:T,val2:T) -> T {
+		x.clamp_impl(val1,val2)
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_clamp_ord() {
+		assert_eq!( clamp_ord(5,0,10), 5 );
+		assert_eq!( clamp_ord(-3,0,10), 0 );
+		assert_eq!( clamp_ord(15_u8,10,0), 10 );
+		assert_eq!( clamp_ord(-3_i64,10,0), 0 );
+		assert_eq!( clamp_ord(7,4,4), 4 );
+		assert_eq!( clamp_ord('z','a','m'), 'm' );
+		assert_eq!( clamp(15.0,10.0,0.0), clamp_ord(15,10,0) as f64 );
+	}
+
 }
-pub use clamp::clamp;
+pub use clamp::{clamp,clamp_ord,clamp01,saturate,clamp_nonneg};
 
 /// `power` 関数を定義するモジュール
 mod power {
@@ -508,3 +750,335 @@ mod power {
 
 }
 pub use power::power;
+
+/// 整数の平方根・立方根 `isqrt` と `icbrt` を定義するモジュール
+mod integer_root {
+
+	/// 符号なし整数に対して、根の整数部分を浮動小数を経由せずに計算するトレイト
+	pub trait IntegerRoot: Sized {
+		fn isqrt_impl(self) -> Self;
+		fn icbrt_impl(self) -> Self;
+	}
+
+	macro_rules! impl_integer_root {
+		( $($t:ty)+ ) => { $(
+			impl IntegerRoot for $t {
+				fn isqrt_impl(self) -> Self {
+					if self<2 { return self; }
+					// 真の平方根以上の2の冪から Newton 法で単調に減少させる
+					let bits = <$t>::BITS - self.leading_zeros();
+					let mut x:$t = 1 << bits.div_ceil(2);
+					loop {
+						let y = (x+self/x)/2;
+						if y>=x { return x; }
+						x = y;
+					}
+				}
+				fn icbrt_impl(self) -> Self {
+					// 上位の桁から1ビットずつ決定する
+					let mut n = self;
+					let mut y:$t = 0;
+					let mut s = (<$t>::BITS-1)/3*3;
+					loop {
+						y <<= 1;
+						let b = 3*y*(y+1)+1;
+						if (n>>s)>=b {
+							n -= b<<s;
+							y += 1;
+						}
+						if s==0 { return y; }
+						s -= 3;
+					}
+				}
+			}
+		)+ };
+	}
+	impl_integer_root!( u8 u16 u32 u64 u128 usize );
+
+	/// 整数の平方根 `⌊√n⌋` を計算します。浮動小数を経由しないので、大きな値でも正確です。
+	#[inline]
+	pub fn isqrt<T: IntegerRoot>(n:T) -> T { n.isqrt_impl() }
+
+	/// 整数の立方根 `⌊∛n⌋` を計算します。浮動小数を経由しないので、大きな値でも正確です。
+	#[inline]
+	pub fn icbrt<T: IntegerRoot>(n:T) -> T { n.icbrt_impl() }
+
+	#[cfg(test)]
+	#[test]
+	fn test_integer_root() {
+		assert_eq!( isqrt(u64::MAX), u32::MAX as u64 );
+		assert_eq!( isqrt(u128::MAX), u64::MAX as u128 );
+		assert_eq!( isqrt(u8::MAX), 15 );
+		assert_eq!( icbrt(u64::MAX), 2_642_245 );
+		assert_eq!( icbrt(u8::MAX), 6 );
+		assert_eq!( icbrt(u128::MAX), 6_981_463_658_331 );
+		for r in 1..=2000_u64 {
+			assert_eq!( (isqrt(r*r),isqrt(r*r-1)), (r,r-1) );
+			assert_eq!( (icbrt(r*r*r),icbrt(r*r*r-1)), (r,r-1) );
+		}
+		for n in 0..=u16::MAX {
+			assert_eq!( isqrt(n), n.isqrt() );
+			let c = icbrt(n) as u64;
+			assert!( c*c*c<=n as u64 && (c+1)*(c+1)*(c+1)>n as u64 );
+		}
+		// 完全平方数の直前は浮動小数を経由すると誤る
+		let r = 4_294_967_295_u64;
+		assert_eq!( isqrt(r*r-1), r-1 );
+	}
+
+}
+pub use integer_root::{isqrt,icbrt};
+
+/// 整数の剰余演算 `pow_mod` と `mul_mod` を定義するモジュール
+mod mod_arith {
+
+	/// 符号なし整数に対して剰余の下での乗算と累乗を定義するトレイト
+	pub trait ModArith: Sized {
+		fn mul_mod_impl(self,rhs:Self,modulus:Self) -> Self;
+		fn pow_mod_impl(self,exp:Self,modulus:Self) -> Self;
+	}
+
+	macro_rules! impl_mod_arith {
+		( $($t:ty)+ ) => { $(
+			impl ModArith for $t {
+				#[inline]
+				fn mul_mod_impl(self,rhs:Self,modulus:Self) -> Self {
+					// u128 で積を計算するのでオーバーフローしない
+					((self as u128 * rhs as u128) % modulus as u128) as $t
+				}
+				fn pow_mod_impl(self,exp:Self,modulus:Self) -> Self {
+					let m = modulus as u128;
+					let mut base = self as u128 % m;
+					let mut exp = exp;
+					let mut result = 1 % m;
+					while exp>0 {
+						if exp&1==1 { result = result*base%m; }
+						base = base*base%m;
+						exp >>= 1;
+					}
+					result as $t
+				}
+			}
+		)+ };
+	}
+	impl_mod_arith!( u8 u16 u32 u64 usize );
+
+	/// `a*b mod m` を計算します。途中の積がオーバーフローしないように `u128` で計算します。
+	/// * `m` が `0` の場合はパニックします。
+	#[inline]
+	pub fn mul_mod<T: ModArith>(a:T,b:T,m:T) -> T { a.mul_mod_impl(b,m) }
+
+	/// `base^exp mod modulus` を繰り返し2乗法により計算します。途中の積がオーバーフローしないように `u128` で計算します。
+	/// * `modulus` が `0` の場合はパニックします。
+	#[inline]
+	pub fn pow_mod<T: ModArith>(base:T,exp:T,modulus:T) -> T { base.pow_mod_impl(exp,modulus) }
+
+	#[cfg(test)]
+	#[test]
+	fn test_mod_arith() {
+		assert_eq!( pow_mod(2_u32,10,1000), 24 );
+		assert_eq!( pow_mod(3_u8,200,251), 149 );
+		assert_eq!( pow_mod(7_u64,0,13), 1 );
+		assert_eq!( pow_mod(7_u64,5,1), 0 );
+		// 2⁶⁴-59 は素数なので、 Fermat の小定理により a^(p-1) ≡ 1
+		let p = u64::MAX-58;
+		assert_eq!( pow_mod(123_456_789_u64,p-1,p), 1 );
+		assert_eq!( mul_mod(u64::MAX-1,u64::MAX-1,u64::MAX), 1 );
+		assert_eq!( mul_mod(200_u8,200,251), ((200*200)%251) as u8 );
+	}
+
+}
+pub use mod_arith::{mul_mod,pow_mod};
+
+/// sinc 関数を定義するモジュール
+mod sinc {
+	use super::*;
+	use norm_sq::NormSq;
+
+	/// `Float` と `Complex` に対して `sinc`, `sinc_normalized` に対応するトレイト
+	pub trait Sinc: Sized {
+		fn sinc_impl(self) -> Self;
+		fn sinc_normalized_impl(self) -> Self;
+	}
+
+	macro_rules! impl_sinc {
+		( $( $t:ty : $pi:expr ),+ ) => { $(
+			impl Sinc for $t {
+				fn sinc_impl(self) -> Self {
+					// 0 の近くでは除算による誤差を避けるため Taylor 展開 `1-x²/6+x⁴/120` を用いる
+					if self.norm_sq_impl()<1e-6 {
+						let x2 = self*self;
+						return 1.0 - x2/6.0*(1.0-x2/20.0);
+					}
+					self.sin()/self
+				}
+				#[inline]
+				fn sinc_normalized_impl(self) -> Self {
+					(self*$pi).sinc_impl()
+				}
+			}
+		)+ };
+	}
+	impl_sinc!(
+		f64: std::f64::consts::PI, f32: std::f32::consts::PI,
+		C<f64>: std::f64::consts::PI, C<f32>: std::f32::consts::PI
+	);
+
+	/// sinc 関数 `sin(x)/x` を計算します。除去可能な特異点 `x=0` では `1` を返します。
+	#[inline]
+	pub fn sinc<T: Sinc>(x:T) -> T { x.sinc_impl() }
+
+	/// 正規化された sinc 関数 `sin(πx)/(πx)` を計算します。 `x=0` では `1` を返し、 `0` 以外の整数では `0` となります。
+	#[inline]
+	pub fn sinc_normalized<T: Sinc>(x:T) -> T { x.sinc_normalized_impl() }
+
+	#[cfg(test)]
+	#[test]
+	fn test_sinc() {
+		use std::f64::consts::PI;
+		assert_eq!( sinc(0.0_f64), 1.0 );
+		assert_eq!( sinc(0.0_f32), 1.0 );
+		assert!( sinc(PI).abs()<1e-15 );
+		assert!( (sinc(PI/2.0)-2.0/PI).abs()<1e-15 );
+		// 0 の近くで連続
+		for x in [1e-8,1e-4,9.99e-4,1.001e-3,1e-2] {
+			assert!( (sinc(x)-x.sin()/x).abs()<1e-15 );
+			assert!( (sinc(-x)-sinc(x)).abs()<1e-15 );
+		}
+		assert!( (sinc(9.999e-4_f32)-sinc(1.0001e-3_f32)).abs()<1e-6 );
+
+		assert_eq!( sinc_normalized(0.0_f64), 1.0 );
+		assert!( sinc_normalized(1.0_f64).abs()<1e-15 );
+		assert!( sinc_normalized(-3.0_f64).abs()<1e-15 );
+		assert!( (sinc_normalized(0.5_f64)-2.0/PI).abs()<1e-15 );
+
+		assert_eq!( sinc(C::new(0.0_f64,0.0)), C::new(1.0,0.0) );
+		assert!( (sinc(C::new(0.0_f64,1.0))-C::new(1.0_f64.sinh(),0.0)).norm()<1e-15 );
+		assert!( (sinc(C::new(1e-4_f64,1e-4))-C::new(1e-4_f64,1e-4).sin()/C::new(1e-4,1e-4)).norm()<1e-15 );
+	}
+
+}
+pub use sinc::{sinc,sinc_normalized};
+
+/// 絶対値の2乗を計算する `norm_sq` 関数を定義するモジュール
+mod norm_sq {
+	use super::*;
+
+	/// `Float` と `Complex` に対して `norm_sq` に対応するトレイト
+	pub trait NormSq: Sized {
+		/// 絶対値の2乗の型 (複素数の場合も実数となる)
+		type Output;
+		fn norm_sq_impl(self) -> Self::Output;
+	}
+
+	macro_rules! impl_norm_sq {
+		( $($t:ty)+ ) => { $(
+			impl NormSq for $t {
+				type Output = $t;
+				#[inline]
+				fn norm_sq_impl(self) -> $t { self * self }
+			}
+			impl NormSq for C<$t> {
+				type Output = $t;
+				#[inline]
+				fn norm_sq_impl(self) -> $t { self.norm_sqr() }
+			}
+		)+ };
+	}
+	impl_norm_sq!( f64 f32 );
+
+	/// 絶対値の2乗 `|z|²` を計算します。平方根を計算しないので `abs` より軽量で、大きさの比較などに用います。
+	/// * 実数 `x` に対しては `x*x` を返します。
+	/// * 複素数 `z` に対しては `re²+im²` を実数として返します。
+	#[inline]
+	pub fn norm_sq<T: NormSq>(x:T) -> T::Output { x.norm_sq_impl() }
+
+	#[cfg(test)]
+	#[test]
+	fn test_norm_sq() {
+		assert_eq!( norm_sq(-3.0_f64), 9.0 );
+		assert_eq!( norm_sq(1.5_f32), 2.25 );
+		assert_eq!( norm_sq(C::new(3.0_f64,-4.0)), 25.0 );
+		assert_eq!( norm_sq(C::new(0.5_f32,1.0)), 1.25 );
+	}
+
+}
+pub use norm_sq::norm_sq;
+
+/// アニメーション等で用いるイージング関数を定義するモジュール
+mod easing {
+	use super::*;
+	use clamp::Clamp;
+
+	/// イージング関数の引数を `[0,1]` の範囲に制限し、定数 `2` と共に返す
+	#[inline]
+	fn prepare<F: Float+Clamp>(t:F) -> (F,F) {
+		(clamp(t,F::zero(),F::one()),F::one()+F::one())
+	}
+
+	/// 2次関数で加速するイージング関数 `t²` です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_in_quad<F: Float+Clamp>(t:F) -> F {
+		let (t,_) = prepare(t);
+		t*t
+	}
+	/// 2次関数で減速するイージング関数 `1-(1-t)²` です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_out_quad<F: Float+Clamp>(t:F) -> F {
+		let (t,_) = prepare(t);
+		let u = F::one()-t;
+		F::one()-u*u
+	}
+	/// 前半は2次関数で加速し、後半は2次関数で減速するイージング関数です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_in_out_quad<F: Float+Clamp>(t:F) -> F {
+		let (t,two) = prepare(t);
+		if t<two.recip() { two*t*t }
+		else {
+			let u = two-two*t;
+			F::one()-u*u/two
+		}
+	}
+	/// 3次関数で加速するイージング関数 `t³` です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_in_cubic<F: Float+Clamp>(t:F) -> F {
+		let (t,_) = prepare(t);
+		t*t*t
+	}
+	/// 3次関数で減速するイージング関数 `1-(1-t)³` です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_out_cubic<F: Float+Clamp>(t:F) -> F {
+		let (t,_) = prepare(t);
+		let u = F::one()-t;
+		F::one()-u*u*u
+	}
+	/// 前半は3次関数で加速し、後半は3次関数で減速するイージング関数です。引数は `[0,1]` に制限されます。
+	#[inline]
+	pub fn ease_in_out_cubic<F: Float+Clamp>(t:F) -> F {
+		let (t,two) = prepare(t);
+		if t<two.recip() { two*two*t*t*t }
+		else {
+			let u = two-two*t;
+			F::one()-u*u*u/two
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_easing() {
+		let functions: [fn(f64)->f64;6] = [ease_in_quad,ease_out_quad,ease_in_out_quad,ease_in_cubic,ease_out_cubic,ease_in_out_cubic];
+		for f in functions {
+			assert_eq!( f(0.0), 0.0 );
+			assert_eq!( f(1.0), 1.0 );
+			assert_eq!( f(-0.5), 0.0 );
+			assert_eq!( f(1.5), 1.0 );
+			assert!( f(0.49)<f(0.5) && f(0.5)<f(0.51) );
+		}
+		assert_eq!( ease_in_out_quad(0.5), 0.5 );
+		assert_eq!( ease_in_out_cubic(0.5_f32), 0.5 );
+		assert_eq!( ease_in_quad(0.5_f32), 0.25 );
+		assert_eq!( ease_out_cubic(0.5), 0.875 );
+	}
+
+}
+pub use easing::{ease_in_quad,ease_out_quad,ease_in_out_quad,ease_in_cubic,ease_out_cubic,ease_in_out_cubic};
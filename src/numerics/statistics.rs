@@ -0,0 +1,292 @@
+//! 統計量の計算に関する関数をまとめたモジュール
+
+use super::*;
+use std::cmp::Ordering;
+
+
+
+/// 分位数と、それを用いた外れ値の処理
+mod quantile {
+	use super::*;
+
+	/// データ `data` の `q` 分位数 (`0≦q≦1`) を線形補間により計算します。
+	/// * 昇順に並べたデータの `q*(n-1)` 番目の位置の値を、前後の値の線形補間により求めます (`numpy.quantile` の既定の方法と同じ)。
+	/// * `q` は `0≦q≦1` の範囲に制限されます。
+	/// * データが空の場合や `q` が NaN の場合は `None` を返します。
+	pub fn quantile<T:Float>(data:&[T],q:T) -> Option<T> {
+		if data.is_empty() || q.is_nan() { return None; }
+		let mut sorted = data.to_vec();
+		sorted.sort_by(|a,b| a.partial_cmp(b).unwrap_or(Ordering::Equal) );
+		Some(sorted_quantile(&sorted,q))
+	}
+
+	/// 昇順に並べられた空でないデータの `q` 分位数を計算する
+	fn sorted_quantile<T:Float>(sorted:&[T],q:T) -> T {
+		let q = q.max(T::zero()).min(T::one());
+		let pos = q * T::from(sorted.len()-1).unwrap();
+		let lower = pos.floor();
+		let i = lower.to_usize().unwrap();
+		match sorted.get(i+1) {
+			Some(&next) => sorted[i] + (next-sorted[i]) * (pos-lower),
+			None => sorted[i]
+		}
+	}
+
+	/// データ `data` の下位 `low_pct` パーセンタイルと上位 `high_pct` パーセンタイルを計算し、全ての値をその範囲に収めた `Vec` を返します (winsorize)。
+	/// * パーセンタイルは `0〜100` で指定し、範囲外の値はその範囲に制限されます。 `low_pct>high_pct` の場合は入れ替えて扱います。
+	/// * データが空の場合や、パーセンタイルに NaN が指定された場合はデータをそのまま複製して返します。
+	pub fn clip_percentiles<T:Float>(data:&[T],low_pct:T,high_pct:T) -> Vec<T> {
+		let mut clipped = data.to_vec();
+		clip_percentiles_in_place(&mut clipped,low_pct,high_pct);
+		clipped
+	}
+
+	/// `clip_percentiles` と同じ処理を、データ `data` を直接書き換えて行います。
+	pub fn clip_percentiles_in_place<T:Float>(data:&mut [T],low_pct:T,high_pct:T) {
+		if data.is_empty() || low_pct.is_nan() || high_pct.is_nan() { return; }
+		let hundred = T::from(100).unwrap();
+		let (low_pct,high_pct) = (low_pct.min(high_pct),low_pct.max(high_pct));
+
+		let mut sorted = data.to_vec();
+		sorted.sort_by(|a,b| a.partial_cmp(b).unwrap_or(Ordering::Equal) );
+		let low = sorted_quantile(&sorted,low_pct/hundred);
+		let high = sorted_quantile(&sorted,high_pct/hundred);
+
+		for v in data.iter_mut() {
+			if *v<low { *v = low; }
+			else if *v>high { *v = high; }
+		}
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_clip_percentiles() {
+		let data = [1.0,2.0,3.0,4.0,5.0];
+		assert_eq!( quantile(&data,0.5), Some(3.0) );
+		assert_eq!( quantile(&data,0.25), Some(2.0) );
+		assert_eq!( quantile(&[1.0,2.0],0.5), Some(1.5) );
+		assert_eq!( quantile::<f64>(&[],0.5), None );
+
+		// 外れ値を含むデータ
+		let data = [-100.0,1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0,100.0];
+		let clipped = clip_percentiles(&data,10.0,90.0);
+		assert_eq!( (quantile(&data,0.1),quantile(&data,0.9)), (Some(1.0),Some(9.0)) );
+		assert_eq!( clipped, vec![1.0,1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0,9.0] );
+		assert_eq!( clip_percentiles(&data,90.0,10.0), clipped );
+
+		let mut in_place = data;
+		clip_percentiles_in_place(&mut in_place,10.0,90.0);
+		assert_eq!( in_place.to_vec(), clipped );
+
+		// 極端なパーセンタイル
+		assert_eq!( clip_percentiles(&data,0.0,100.0), data.to_vec() );
+		assert_eq!( clip_percentiles(&data,-10.0,200.0), data.to_vec() );
+		assert_eq!( clip_percentiles(&data,50.0,50.0), vec![5.0;11] );
+		assert_eq!( clip_percentiles::<f64>(&[],10.0,90.0), vec![] );
+	}
+
+}
+pub use quantile::{quantile,clip_percentiles,clip_percentiles_in_place};
+
+
+
+/// 平均値と重み付き平均値
+mod mean {
+	use super::*;
+
+	/// 値 `values` の算術平均 `Σx/n` を計算します。要素がない場合は `None` を返します。
+	/// * `Statistics::mean` と同じく、 Welford のアルゴリズムにより逐次更新して計算します。
+	pub fn mean_of<T:Float>(values:impl IntoIterator<Item=T>) -> Option<T> {
+		values.mean()
+	}
+
+	/// 値と重みの組 `(x,w)` から重み付き平均 `Σ(w·x)/Σw` を計算します。
+	/// * 要素がない場合や、重みの合計が `0` の場合は `None` を返します。
+	pub fn weighted_mean<T:Float>(pairs:impl IntoIterator<Item=(T,T)>) -> Option<T> {
+		let (sum,weight) = pairs.into_iter().fold((T::zero(),T::zero()),|(s,ws),(x,w)| (s+w*x,ws+w) );
+		(!weight.is_zero()).then(|| sum / weight )
+	}
+
+	#[cfg(feature="iterator")]
+	/// 別々に与えた値 `values` と重み `weights` から重み付き平均 `Σ(w·x)/Σw` を計算します。
+	/// * 要素がない場合や、重みの合計が `0` の場合は `None` を返します。
+	/// * 値と重みの要素数が一致しない場合はパニックします。
+	pub fn weighted_mean_of<T:Float>(values:impl IntoIterator<Item=T>,weights:impl IntoIterator<Item=T>) -> Option<T> {
+		use crate::iterator::zip::for_iters::IntoZip;
+		weighted_mean( (values.into_iter(),weights.into_iter()).zip_eq() )
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_weighted_mean() {
+		assert_eq!( mean_of([1.0,2.0,3.0,6.0]), Some(3.0) );
+		assert_eq!( mean_of(Vec::<f64>::new()), None );
+
+		// (1*1 + 2*3 + 4*4) / (1+3+4) = 23/8
+		assert_eq!( weighted_mean([(1.0,1.0),(2.0,3.0),(4.0,4.0)]), Some(2.875) );
+		assert_eq!( weighted_mean([(1.0,0.0),(2.0,0.0)]), None );
+		assert_eq!( weighted_mean(Vec::<(f32,f32)>::new()), None );
+		#[cfg(feature="iterator")]
+		assert_eq!( weighted_mean_of([1.0,2.0,4.0],[1.0,3.0,4.0]), Some(2.875) );
+	}
+
+}
+pub use mean::{mean_of,weighted_mean};
+#[cfg(feature="iterator")]
+pub use mean::weighted_mean_of;
+
+
+
+/// 分散と標準偏差 (実数と複素数の双方に対応)
+mod variance {
+	use super::*;
+	use std::ops::{Add,Sub,Div};
+
+	/// 分散を計算できるデータの要素の型を定義するトレイト
+	pub trait VarianceElement: Copy + Add<Output=Self> + Sub<Output=Self> + Div<Self::Real,Output=Self> {
+		/// 分散の型 (複素数の場合も実数となる)
+		type Real: Float;
+		/// 加法の単位元
+		fn zero_element() -> Self;
+		/// 絶対値の2乗 `|x|²`
+		fn abs_sq(self) -> Self::Real;
+	}
+
+	macro_rules! impl_variance_element {
+		( $($t:ty)+ ) => { $(
+			impl VarianceElement for $t {
+				type Real = $t;
+				#[inline]
+				fn zero_element() -> Self { 0.0 }
+				#[inline]
+				fn abs_sq(self) -> $t { self*self }
+			}
+			impl VarianceElement for Complex<$t> {
+				type Real = $t;
+				#[inline]
+				fn zero_element() -> Self { Complex::new(0.0,0.0) }
+				#[inline]
+				fn abs_sq(self) -> $t { self.norm_sqr() }
+			}
+		)+ };
+	}
+	impl_variance_element!( f64 f32 );
+
+	/// 平均からの偏差の2乗和 `Σ|x-μ|²` を計算します。複素数の場合も実数で返します。データが空の場合は `None` を返します。
+	pub fn sum_of_squares<T:VarianceElement>(data:&[T]) -> Option<T::Real> {
+		if data.is_empty() { return None; }
+		let n = <T::Real as NumCast>::from(data.len()).unwrap();
+		let mean = data.iter().fold(T::zero_element(),|s,&x| s+x ) / n;
+		Some( data.iter().fold(T::Real::zero(),|s,&x| s+(x-mean).abs_sq() ) )
+	}
+
+	/// データ `data` の (母) 分散 `E[|x-μ|²]` を計算します。データが空の場合は `None` を返します。
+	/// * 複素数のデータに対しては偏差の絶対値の2乗の平均を実数として返します。
+	/// * スライスを2回走査して計算します。実数のイテレータを1回の走査で処理する場合は `Statistics::variance` を使用します。
+	pub fn variance<T:VarianceElement>(data:&[T]) -> Option<T::Real> {
+		let n = <T::Real as NumCast>::from(data.len()).unwrap();
+		sum_of_squares(data).map(|s| s/n )
+	}
+
+	/// データ `data` の (母) 標準偏差を計算します。データが空の場合は `None` を返します。
+	/// * `variance` と同じく、スライスを2回走査して計算します。複素数のデータにも対応します。
+	pub fn std_dev<T:VarianceElement>(data:&[T]) -> Option<T::Real> {
+		variance(data).map(|v| v.sqrt() )
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_variance() {
+		assert_eq!( variance(&[2.0,4.0,4.0,4.0,5.0,5.0,7.0,9.0]), Some(4.0) );
+		assert_eq!( std_dev(&[2.0_f32,4.0,4.0,4.0,5.0,5.0,7.0,9.0]), Some(2.0) );
+		assert_eq!( variance::<f64>(&[]), None );
+
+		let z = [Complex::new(1.0,2.0),Complex::new(-1.0,0.0),Complex::new(3.0,-2.0),Complex::new(1.0,4.0)];
+		// 平均は 1+i
+		let mean = Complex::new(1.0,1.0);
+		let manual = z.iter().map(|&x| { let d:Complex<f64> = x-mean; d.re*d.re+d.im*d.im } ).sum::<f64>();
+		assert_eq!( sum_of_squares(&z), Some(manual) );
+		assert_eq!( variance(&z), Some(manual/4.0) );
+		assert_eq!( std_dev(&z), Some((manual/4.0).sqrt()) );
+	}
+
+}
+pub use variance::{VarianceElement,sum_of_squares,variance,std_dev};
+
+
+
+/// Welford のアルゴリズムにより、イテレータを1回走査するだけで平均と分散を数値的に安定に計算する
+mod welford {
+	use super::*;
+
+	/// 要素数・平均・偏差の2乗和 `(n,μ,Σ(x-μ)²)` を1回の走査で計算する。要素がない場合は `None` を返す。
+	fn accumulate<F:Float>(values:impl IntoIterator<Item=F>) -> Option<(F,F,F)> {
+		let (n,mean,m2) = values.into_iter()
+		.fold((F::zero(),F::zero(),F::zero()),|(n,mean,m2),x| {
+			let n = n + F::one();
+			let delta = x - mean;
+			let mean = mean + delta/n;
+			(n,mean,m2+delta*(x-mean))
+		});
+		(!n.is_zero()).then_some((n,mean,m2))
+	}
+
+	/// 浮動小数点数のイテレータに対して、平均・分散・標準偏差を1回の走査で計算するメソッドを提供するトレイト
+	/// * 2回走査する公式と異なり、平均を逐次更新しながら偏差の2乗和を累積するため、桁落ちが生じにくい。
+	/// * 要素がない場合は `None` を返します。
+	/// * 複素数には対応しません。複素数のデータやスライスの分散は、2回走査する `variance`, `std_dev` 関数で計算できます。
+	pub trait Statistics<F:Float>: IntoIterator<Item=F> + Sized {
+		/// 算術平均 `Σx/n` を計算します。
+		fn mean(self) -> Option<F> {
+			accumulate(self).map(|(_,mean,_)| mean )
+		}
+		/// 母分散 `Σ(x-μ)²/n` を計算します。
+		fn variance(self) -> Option<F> {
+			accumulate(self).map(|(n,_,m2)| m2/n )
+		}
+		/// 標本分散 (不偏分散) `Σ(x-μ)²/(n-1)` を計算します。要素が1つ以下の場合は `None` を返します。
+		fn sample_variance(self) -> Option<F> {
+			accumulate(self)
+			.filter(|(n,_,_)| *n>F::one() )
+			.map(|(n,_,m2)| m2/(n-F::one()) )
+		}
+		/// 母標準偏差を計算します。
+		fn std_dev(self) -> Option<F> {
+			self.variance().map(|v| v.sqrt() )
+		}
+		/// 標本標準偏差を計算します。要素が1つ以下の場合は `None` を返します。
+		fn sample_std_dev(self) -> Option<F> {
+			self.sample_variance().map(|v| v.sqrt() )
+		}
+	}
+	impl<F:Float,I:IntoIterator<Item=F>> Statistics<F> for I {}
+
+	#[cfg(test)]
+	#[test]
+	fn test_statistics() {
+		let close = |a:f64,b:f64| (a-b).abs()<=1e-12*b.abs().max(1.0) ;
+		let data = [2.0,4.0,4.0,4.0,5.0,5.0,7.0,9.0];
+		assert_eq!( data.mean(), Some(5.0) );
+		assert_eq!( data.variance(), Some(4.0) );
+		assert_eq!( data.std_dev(), Some(2.0) );
+		assert!( close(data.sample_variance().unwrap(),32.0/7.0) );
+
+		// 2回走査する公式と比較する
+		let data = (0..1000).map(|i| 1e6 + ((i*37)%101) as f64 * 0.01 ).collect::<Vec<_>>();
+		let n = data.len() as f64;
+		let mean = data.iter().sum::<f64>()/n;
+		let m2 = data.iter().map(|x| (x-mean)*(x-mean) ).sum::<f64>();
+		assert!( close(data.iter().copied().mean().unwrap(),mean) );
+		assert!( (data.iter().copied().variance().unwrap()-m2/n).abs()<1e-9 );
+		assert!( (data.iter().copied().sample_std_dev().unwrap()-(m2/(n-1.0)).sqrt()).abs()<1e-9 );
+
+		assert_eq!( [3.5_f32;10].variance(), Some(0.0) );
+		assert_eq!( [3.5_f32;10].std_dev(), Some(0.0) );
+		assert_eq!( [1e8;5].sample_variance(), Some(0.0) );
+		assert_eq!( Vec::<f64>::new().mean(), None );
+		assert_eq!( Vec::<f64>::new().variance(), None );
+		assert_eq!( [1.0].sample_variance(), None );
+	}
+
+}
+pub use welford::Statistics;
@@ -3,4 +3,13 @@ pub use crate::iterator::{
 	zip::for_prelude::*,
 	product::for_prelude::*,
 	chain::for_prelude::*,
+	misc::for_prelude::*,
 };
+pub use crate::tuples::{ZipOptions,ZipResults};
+pub use crate::logging::for_prelude::*;
+pub use crate::misc::ValidationError;
+
+#[cfg(feature="numerics")]
+pub use crate::numerics::{GcdLcm,IndexWrap,powi,lerp,inverse_lerp,remap};
+#[cfg(feature="numerics")]
+pub use crate::numerics::primitive_functions::{signum,copysign,abs,recip};
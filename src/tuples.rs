@@ -5,7 +5,13 @@ mod options {
 		fn zip_options(self) -> Option<T>;
 	}
 
+	pub trait ZipResults<T,E> {
+		/// 複数の Result 型を含む型を1つの Result 型に変換します。要素のうち1つでも Err があれば、最初の Err になります
+		fn zip_results(self) -> Result<T,E>;
+	}
+
 	/// * `(Option<T1>,Option<T2>,...)` を `Option<(T1,T2,...)>` に変換するトレイト `ZipOptions` の実装をまとめて行うマクロ
+	/// * 同時に `(Result<T1,E>,Result<T2,E>,...)` を `Result<(T1,T2,...),E>` に変換するトレイト `ZipResults` も実装する
 	/// * `impl_zip_options!( T0 0 T1 1 T2 2 ... T(N-1) (N-1) )` と指定すれば、 `N` 個の要素まで対応する
 	macro_rules! impl_zip_options {
 		( $( $t:ident $n:tt )+ ) => {
@@ -25,6 +31,11 @@ mod options {
 					Some( ( $(self.$n?,)+ ) )
 				}
 			}
+			impl<$($t),+,E> ZipResults<($($t,)+),E> for ($(Result<$t,E>,)+) {
+				fn zip_results(self) -> Result<($($t,)+),E> {
+					Ok( ( $(self.$n?,)+ ) )
+				}
+			}
 		};
 		(@each | ) => {};
 	}
@@ -39,6 +50,16 @@ mod options {
 		}
 	}
 
+	#[cfg(test)]
+	#[test]
+	fn test_zip_results() {
+		type R<T> = Result<T,&'static str>;
+		assert_eq!( (R::Ok(1),R::Ok("a"),R::Ok(2.5)).zip_results(), Ok((1,"a",2.5)) );
+		assert_eq!( (R::Ok(1),R::<i32>::Err("second"),R::<char>::Err("third"),R::Ok(4)).zip_results(), Err("second") );
+		assert_eq!( (Some(1),Some('x'),Some(3),Some(4),Some(5),Some(6),Some(7),Some(8),Some(9),Some(10),Some(11),Some(12)).zip_options(), Some((1,'x',3,4,5,6,7,8,9,10,11,12)) );
+		assert_eq!( (Some(1),None::<i32>,Some(3)).zip_options(), None );
+	}
+
 }
 pub use options::*;
 
@@ -53,7 +74,19 @@ mod tuple_to_array {
 		fn to_array(self) -> [T;N];
 	}
 
-	/// * タプルを配列に変換するトレイト `TupleToArray` の実装をまとめて行うマクロ
+	/// 配列を同一要素からなるタプルに変換します (`TupleToArray` の逆変換)
+	pub trait FromArray<T> {
+		/// 配列をタプルに変換します
+		fn into_tuple(self) -> T;
+	}
+
+	/// 同一要素からなるタプルの各要素に関数を適用します
+	pub trait TupleMap<T,const N:usize> {
+		/// タプルの各要素に関数 `f` を順に適用し、結果を配列として返します
+		fn map_each<U,F>(self,f:F) -> [U;N] where F: FnMut(T) -> U;
+	}
+
+	/// * タプルを配列に変換するトレイト `TupleToArray` と、その逆変換 `FromArray` 、各要素に関数を適用するトレイト `TupleMap` の実装をまとめて行うマクロ
 	/// * `impl_tuple_to_array!(indices: 0 1 2 ... N )` と指定すれば、 `N` 個の要素まで対応する
 	macro_rules! impl_tuple_to_array {
 		(indices: $i0:tt $($i:tt)+ ) => {
@@ -70,12 +103,48 @@ mod tuple_to_array {
 				}
 			}
 
+			impl<$t> FromArray<($($tx,)+)> for [$t;$y0] {
+				fn into_tuple(self) -> ($($tx,)+) {
+					// 全要素が `Some(..)` で、各要素を1度ずつ取り出すので、安全にアンラップできる
+					let mut a = self.map(Some);
+					( $( a[$x].take().unwrap() ,)+ )
+				}
+			}
+
+			impl<$t> TupleMap<$t,$y0> for ($($tx,)+) {
+				fn map_each<U,F>(self,f:F) -> [U;$y0] where F: FnMut($t) -> U {
+					self.to_array().map(f)
+				}
+			}
+
 			impl_tuple_to_array! {@each $t $($tx $x,)+ $t $y0 | $($y),* }
 		};
 		(@each $t:ident $($tx:ident $x:tt),+ | ) => {};
 	}
 	pub(crate) use impl_tuple_to_array;
 
+	#[cfg(test)]
+	#[test]
+	fn test_map_each() {
+		assert_eq!( (1,2).map_each(|x| x*10 ), [10,20] );
+		assert_eq!( ("a","bc","def").map_each(str::len), [1,2,3] );
+		let mut count = 0;
+		let r = (1.5,2.5,-3.0,4.0,0.0).map_each(|x:f64| { count += 1; x.to_string() } );
+		assert_eq!( r, ["1.5","2.5","-3","4","0"] );
+		assert_eq!( count, 5 );
+	}
+
+	#[cfg(test)]
+	#[test]
+	fn test_into_tuple() {
+		assert_eq!( [1,2,3].into_tuple(), (1,2,3) );
+		assert_eq!( ["a"].into_tuple(), ("a",) );
+		let t = (1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0,10.0,11.0,12.0);
+		assert_eq!( t.to_array().into_tuple(), t );
+		let a = [String::from("x"),String::from("y")];
+		assert_eq!( a.clone().into_tuple().to_array(), a );
+	}
+
 }
 pub use tuple_to_array::*;
 